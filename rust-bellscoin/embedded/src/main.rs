@@ -46,7 +46,7 @@ fn main() -> ! {
 
     // Derive address
     let pubkey = pk.public_key(&secp);
-    let address = Address::p2wpkh(&pubkey, Network::Bitcoin).unwrap();
+    let address = Address::p2wpkh(&pubkey, Network::Bellscoin).unwrap();
     hprintln!("Address: {}", address).unwrap();
 
     assert_eq!(address.to_string(), "bc1qpx9t9pzzl4qsydmhyt6ctrxxjd4ep549np9993".to_string());
@@ -40,13 +40,17 @@ pub const MIN_TRANSACTION_WEIGHT: u32 = 4 * 60;
 pub const WITNESS_SCALE_FACTOR: usize = 4;
 /// The maximum allowed number of signature check operations in a block
 pub const MAX_BLOCK_SIGOPS_COST: i64 = 80_000;
-/// Mainnet (bitcoin) pubkey address prefix.
+/// Mainnet (bellscoin) pubkey address prefix.
+#[deprecated(since = "0.29.2", note = "Please use `Network::pubkey_address_prefix` instead.")]
 pub const PUBKEY_ADDRESS_PREFIX_MAIN: u8 = 25;
-/// Mainnet (bitcoin) script address prefix.
+/// Mainnet (bellscoin) script address prefix.
+#[deprecated(since = "0.29.2", note = "Please use `Network::script_address_prefix` instead.")]
 pub const SCRIPT_ADDRESS_PREFIX_MAIN: u8 = 30;
-/// Test (tesnet, signet, regtest) pubkey address prefix.
-pub const PUBKEY_ADDRESS_PREFIX_TEST: u8 = 0x71; 
-/// Test (tesnet, signet, regtest) script address prefix.
+/// Test (tesnet, regtest) pubkey address prefix.
+#[deprecated(since = "0.29.2", note = "Please use `Network::pubkey_address_prefix` instead.")]
+pub const PUBKEY_ADDRESS_PREFIX_TEST: u8 = 0x71;
+/// Test (tesnet, regtest) script address prefix.
+#[deprecated(since = "0.29.2", note = "Please use `Network::script_address_prefix` instead.")]
 pub const SCRIPT_ADDRESS_PREFIX_TEST: u8 = 0xc4;
 /// The maximum allowed script size.
 pub const MAX_SCRIPT_ELEMENT_SIZE: usize = 520;
@@ -67,8 +71,9 @@ pub fn max_money(_: Network) -> u64 {
     21_000_000 * COIN_VALUE
 }
 
-/// Constructs and returns the coinbase (and only) transaction of the Bitcoin genesis block
-fn bitcoin_genesis_tx() -> Transaction {
+/// Constructs and returns the coinbase (and only) transaction of the Bellscoin genesis block.
+/// All networks share it and differ in block header only
+fn bellscoin_genesis_tx() -> Transaction {
     // Base
     let mut ret = Transaction {
         version: 1,
@@ -80,7 +85,7 @@ fn bitcoin_genesis_tx() -> Transaction {
     // Inputs
     let in_script = script::Builder::new().push_scriptint(486604799)
                                           .push_scriptint(4)
-                                          .push_slice(b"The Times 03/Jan/2009 Chancellor on brink of second bailout for banks")
+                                          .push_slice(b"Nintondo")
                                           .into_script();
     ret.input.push(TxIn {
         previous_output: OutPoint::null(),
@@ -91,14 +96,14 @@ fn bitcoin_genesis_tx() -> Transaction {
 
     // Outputs
     let script_bytes: Result<Vec<u8>, hex::Error> =
-        HexIterator::new("04678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5f").unwrap()
+        HexIterator::new("040184710fa689ad5023690c80f3a49c8f13f8d45b8c857fbcbc8bc4a8e4d3eb4b10f4d4604fa08dce601aaf0f470216fe1b51850b4acf21b179c45070ac7b03a9").unwrap()
             .collect();
     let out_script = script::Builder::new()
         .push_slice(script_bytes.unwrap().as_slice())
         .push_opcode(opcodes::all::OP_CHECKSIG)
         .into_script();
     ret.output.push(TxOut {
-        value: 50 * COIN_VALUE,
+        value: 88 * COIN_VALUE,
         script_pubkey: out_script
     });
 
@@ -108,49 +113,38 @@ fn bitcoin_genesis_tx() -> Transaction {
 
 /// Constructs and returns the genesis block
 pub fn genesis_block(network: Network) -> Block {
-    let txdata = vec![bitcoin_genesis_tx()];
+    let txdata = vec![bellscoin_genesis_tx()];
     let hash: sha256d::Hash = txdata[0].txid().into();
     let merkle_root = hash.into();
     match network {
-        Network::Bitcoin => {
+        Network::Bellscoin => {
             Block {
                 header: BlockHeader {
                     version: 1,
                     prev_blockhash: Hash::all_zeros(),
                     merkle_root,
-                    time: 1231006505,
-                    bits: 0x1d00ffff,
-                    nonce: 2083236893
+                    time: 1383509530,
+                    bits: 0x1e0ffff0,
+                    nonce: 44481
                 },
                 txdata,
             }
         }
+        // header of Dogecoin testnet genesis block, with Bellscoin coinbase
         Network::Testnet => {
             Block {
                 header: BlockHeader {
                     version: 1,
                     prev_blockhash: Hash::all_zeros(),
                     merkle_root,
-                    time: 1296688602,
-                    bits: 0x1d00ffff,
-                    nonce: 414098458
-                },
-                txdata,
-            }
-        }
-        Network::Signet => {
-            Block {
-                header: BlockHeader {
-                    version: 1,
-                    prev_blockhash: Hash::all_zeros(),
-                    merkle_root,
-                    time: 1598918400,
-                    bits: 0x1e0377ae,
-                    nonce: 52613770
+                    time: 1391503289,
+                    bits: 0x1e0ffff0,
+                    nonce: 997879
                 },
                 txdata,
             }
         }
+        // header of Dogecoin regtest genesis block, with Bellscoin coinbase
         Network::Regtest => {
             Block {
                 header: BlockHeader {
@@ -167,11 +161,9 @@ pub fn genesis_block(network: Network) -> Block {
     }
 }
 
-// Mainnet value can be verified at https://github.com/lightning/bolts/blob/master/00-introduction.md
-const GENESIS_BLOCK_HASH_BITCOIN: [u8; 32] = [111, 226, 140, 10, 182, 241, 179, 114, 193, 166, 162, 70, 174, 99, 247, 79, 147, 30, 131, 101, 225, 90, 8, 156, 104, 214, 25, 0, 0, 0, 0, 0];
-const GENESIS_BLOCK_HASH_TESTNET: [u8; 32] = [67, 73, 127, 215, 248, 38, 149, 113, 8, 244, 163, 15, 217, 206, 195, 174, 186, 121, 151, 32, 132, 233, 14, 173, 1, 234, 51, 9, 0, 0, 0, 0];
-const GENESIS_BLOCK_HASH_SIGNET: [u8; 32] = [246, 30, 238, 59, 99, 163, 128, 164, 119, 160, 99, 175, 50, 178, 187, 201, 124, 159, 249, 240, 31, 44, 66, 37, 233, 115, 152, 129, 8, 0, 0, 0];
-const GENESIS_BLOCK_HASH_REGTEST: [u8; 32] = [6, 34, 110, 70, 17, 26, 11, 89, 202, 175, 18, 96, 67, 235, 91, 191, 40, 195, 79, 58, 94, 51, 42, 31, 199, 178, 183, 60, 241, 136, 145, 15];
+const GENESIS_BLOCK_HASH_BELLSCOIN: [u8; 32] = [152, 6, 254, 154, 146, 251, 30, 237, 27, 80, 72, 235, 248, 248, 72, 105, 41, 97, 169, 189, 6, 47, 92, 209, 130, 58, 196, 87, 223, 36, 190, 229];
+const GENESIS_BLOCK_HASH_TESTNET: [u8; 32] = [158, 85, 80, 115, 208, 196, 243, 100, 86, 219, 137, 81, 244, 73, 112, 77, 84, 77, 40, 38, 217, 170, 96, 99, 107, 64, 55, 70, 38, 120, 10, 187];
+const GENESIS_BLOCK_HASH_REGTEST: [u8; 32] = [165, 115, 233, 28, 23, 114, 7, 108, 13, 64, 247, 14, 68, 8, 200, 58, 49, 112, 95, 41, 106, 230, 231, 98, 157, 74, 220, 181, 163, 96, 33, 61];
 
 /// The uniquely identifying hash of the target blockchain.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -186,9 +178,8 @@ impl ChainHash {
     /// for specification.
     pub fn using_genesis_block(network: Network) -> Self {
         match network {
-            Network::Bitcoin => ChainHash(GENESIS_BLOCK_HASH_BITCOIN),
+            Network::Bellscoin => ChainHash(GENESIS_BLOCK_HASH_BELLSCOIN),
             Network::Testnet => ChainHash(GENESIS_BLOCK_HASH_TESTNET),
-            Network::Signet => ChainHash(GENESIS_BLOCK_HASH_SIGNET),
             Network::Regtest => ChainHash(GENESIS_BLOCK_HASH_REGTEST),
         }
     }
@@ -203,38 +194,47 @@ mod test {
     use crate::blockdata::locktime::PackedLockTime;
 
     #[test]
-    fn bitcoin_genesis_first_transaction() {
-        let gen = bitcoin_genesis_tx();
+    #[allow(deprecated)]
+    fn address_prefixes_match_network() {
+        assert_eq!(PUBKEY_ADDRESS_PREFIX_MAIN, Network::Bellscoin.pubkey_address_prefix());
+        assert_eq!(SCRIPT_ADDRESS_PREFIX_MAIN, Network::Bellscoin.script_address_prefix());
+        assert_eq!(PUBKEY_ADDRESS_PREFIX_TEST, Network::Testnet.pubkey_address_prefix());
+        assert_eq!(SCRIPT_ADDRESS_PREFIX_TEST, Network::Testnet.script_address_prefix());
+    }
+
+    #[test]
+    fn bellscoin_genesis_first_transaction() {
+        let gen = bellscoin_genesis_tx();
 
         assert_eq!(gen.version, 1);
         assert_eq!(gen.input.len(), 1);
         assert_eq!(gen.input[0].previous_output.txid, Hash::all_zeros());
         assert_eq!(gen.input[0].previous_output.vout, 0xFFFFFFFF);
         assert_eq!(serialize(&gen.input[0].script_sig),
-                   Vec::from_hex("4d04ffff001d0104455468652054696d65732030332f4a616e2f32303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f757420666f722062616e6b73").unwrap());
+                   Vec::from_hex("1004ffff001d0104084e696e746f6e646f").unwrap());
 
         assert_eq!(gen.input[0].sequence, Sequence::MAX);
         assert_eq!(gen.output.len(), 1);
         assert_eq!(serialize(&gen.output[0].script_pubkey),
-                   Vec::from_hex("434104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac").unwrap());
-        assert_eq!(gen.output[0].value, 50 * COIN_VALUE);
+                   Vec::from_hex("4341040184710fa689ad5023690c80f3a49c8f13f8d45b8c857fbcbc8bc4a8e4d3eb4b10f4d4604fa08dce601aaf0f470216fe1b51850b4acf21b179c45070ac7b03a9ac").unwrap());
+        assert_eq!(gen.output[0].value, 88 * COIN_VALUE);
         assert_eq!(gen.lock_time, PackedLockTime::ZERO);
 
-        assert_eq!(gen.wtxid().to_hex(), "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b");
+        assert_eq!(gen.wtxid().to_hex(), "5b2a3f53f605d62c53e62932dac6925e3d74afa5a4b459745c36d42d0ed26a69");
     }
 
     #[test]
-    fn bitcoin_genesis_full_block() {
-        let gen = genesis_block(Network::Bitcoin);
+    fn bellscoin_genesis_full_block() {
+        let gen = genesis_block(Network::Bellscoin);
 
         assert_eq!(gen.header.version, 1);
         assert_eq!(gen.header.prev_blockhash, Hash::all_zeros());
-        assert_eq!(gen.header.merkle_root.to_hex(), "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b");
+        assert_eq!(gen.header.merkle_root.to_hex(), "5b2a3f53f605d62c53e62932dac6925e3d74afa5a4b459745c36d42d0ed26a69");
 
-        assert_eq!(gen.header.time, 1231006505);
-        assert_eq!(gen.header.bits, 0x1d00ffff);
-        assert_eq!(gen.header.nonce, 2083236893);
-        assert_eq!(gen.header.block_hash().to_hex(), "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f");
+        assert_eq!(gen.header.time, 1383509530);
+        assert_eq!(gen.header.bits, 0x1e0ffff0);
+        assert_eq!(gen.header.nonce, 44481);
+        assert_eq!(gen.header.block_hash().to_hex(), "e5be24df57c43a82d15c2f06bda961296948f8f8eb48501bed1efb929afe0698");
     }
 
     #[test]
@@ -242,23 +242,23 @@ mod test {
         let gen = genesis_block(Network::Testnet);
         assert_eq!(gen.header.version, 1);
         assert_eq!(gen.header.prev_blockhash, Hash::all_zeros());
-        assert_eq!(gen.header.merkle_root.to_hex(), "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b");
-        assert_eq!(gen.header.time, 1296688602);
-        assert_eq!(gen.header.bits, 0x1d00ffff);
-        assert_eq!(gen.header.nonce, 414098458);
-        assert_eq!(gen.header.block_hash().to_hex(), "000000000933ea01ad0ee984209779baaec3ced90fa3f408719526f8d77f4943");
+        assert_eq!(gen.header.merkle_root.to_hex(), "5b2a3f53f605d62c53e62932dac6925e3d74afa5a4b459745c36d42d0ed26a69");
+        assert_eq!(gen.header.time, 1391503289);
+        assert_eq!(gen.header.bits, 0x1e0ffff0);
+        assert_eq!(gen.header.nonce, 997879);
+        assert_eq!(gen.header.block_hash().to_hex(), "bb0a78264637406b6360aad926284d544d7049f45189db5664f3c4d07350559e");
     }
 
     #[test]
-    fn signet_genesis_full_block() {
-        let gen = genesis_block(Network::Signet);
+    fn regtest_genesis_full_block() {
+        let gen = genesis_block(Network::Regtest);
         assert_eq!(gen.header.version, 1);
         assert_eq!(gen.header.prev_blockhash, Hash::all_zeros());
-        assert_eq!(gen.header.merkle_root.to_hex(), "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b");
-        assert_eq!(gen.header.time, 1598918400);
-        assert_eq!(gen.header.bits, 0x1e0377ae);
-        assert_eq!(gen.header.nonce, 52613770);
-        assert_eq!(gen.header.block_hash().to_hex(), "00000008819873e925422c1ff0f99f7cc9bbb232af63a077a480a3633bee1ef6");
+        assert_eq!(gen.header.merkle_root.to_hex(), "5b2a3f53f605d62c53e62932dac6925e3d74afa5a4b459745c36d42d0ed26a69");
+        assert_eq!(gen.header.time, 1296688602);
+        assert_eq!(gen.header.bits, 0x207fffff);
+        assert_eq!(gen.header.nonce, 2);
+        assert_eq!(gen.header.block_hash().to_hex(), "3d2160a3b5dc4a9d62e7e66a295f70313ac808440ef7400d6c0772171ce973a5");
     }
 
     // The *_chain_hash tests are sanity/regression tests, they verify that the const byte array
@@ -291,18 +291,15 @@ mod test {
     }
 
     chain_hash_genesis_block! {
-        mainnet_chain_hash_genesis_block, Network::Bitcoin;
+        mainnet_chain_hash_genesis_block, Network::Bellscoin;
         testnet_chain_hash_genesis_block, Network::Testnet;
-        signet_chain_hash_genesis_block, Network::Signet;
         regtest_chain_hash_genesis_block, Network::Regtest;
     }
 
-    // Test vector taken from: https://github.com/lightning/bolts/blob/master/00-introduction.md
     #[test]
     fn mainnet_chain_hash_test_vector() {
-        let got = ChainHash::using_genesis_block(Network::Bitcoin).to_hex();
-        let want = "6fe28c0ab6f1b372c1a6a246ae63f74f931e8365e15a089c68d6190000000000";
+        let got = ChainHash::using_genesis_block(Network::Bellscoin).to_hex();
+        let want = "9806fe9a92fb1eed1b5048ebf8f848692961a9bd062f5cd1823ac457df24bee5";
         assert_eq!(got, want);
     }
 }
-
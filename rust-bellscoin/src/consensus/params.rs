@@ -1,35 +1,28 @@
 // Written in 2014 by Andrew Poelstra <apoelstra@wpsoftware.net>
 // SPDX-License-Identifier: CC0-1.0
 
-//! Bitcoin consensus parameters.
+//! Bellscoin consensus parameters.
 //!
-//! This module provides a predefined set of parameters for different Bitcoin
+//! This module provides a predefined set of parameters for different Bellscoin
 //! chains (such as mainnet, testnet).
 //!
 
 use crate::network::constants::Network;
 use crate::util::uint::Uint256;
 
-/// Lowest possible difficulty for Mainnet. See comment on Params::pow_limit for more info.
-const MAX_BITS_BITCOIN: Uint256 = Uint256([
+/// Lowest possible difficulty for Mainnet (scrypt, compact 0x1e0fffff). See comment on Params::pow_limit for more info.
+const MAX_BITS_BELLSCOIN: Uint256 = Uint256([
     0x0000000000000000u64,
     0x0000000000000000u64,
     0x0000000000000000u64,
-    0x00000000ffff0000u64,
+    0x00000fffff000000u64,
 ]);
 /// Lowest possible difficulty for Testnet. See comment on Params::pow_limit for more info.
 const MAX_BITS_TESTNET: Uint256 = Uint256([
     0x0000000000000000u64,
     0x0000000000000000u64,
     0x0000000000000000u64,
-    0x00000000ffff0000u64,
-]);
-/// Lowest possible difficulty for Signet. See comment on Params::pow_limit for more info.
-const MAX_BITS_SIGNET: Uint256 = Uint256([
-    0x0000000000000000u64,
-    0x0000000000000000u64,
-    0x0000000000000000u64,
-    0x00000377ae000000u64,
+    0x00000fffff000000u64,
 ]);
 /// Lowest possible difficulty for Regtest. See comment on Params::pow_limit for more info.
 const MAX_BITS_REGTEST: Uint256 = Uint256([
@@ -81,48 +74,37 @@ impl Params {
     /// Creates parameters set for the given network.
     pub fn new(network: Network) -> Self {
         match network {
-            Network::Bitcoin => Params {
-                network: Network::Bitcoin,
-                bip16_time: 1333238400,                 // Apr 1 2012
-                bip34_height: 227931, // 000000000000024b89b42a942fe0d9fea3bb44ab7bd1b19115dd6a759c0808b8
-                bip65_height: 388381, // 000000000000000004c2b624ed5d7756c508d90fd0da2c7c679febfa6c4735f0
-                bip66_height: 363725, // 00000000000000000379eaa19dce8c9b722d46ae6a57c2f1a988119488b50931
-                rule_change_activation_threshold: 1916, // 95%
-                miner_confirmation_window: 2016,
-                pow_limit: MAX_BITS_BITCOIN,
-                pow_target_spacing: 10 * 60,            // 10 minutes.
-                pow_target_timespan: 14 * 24 * 60 * 60, // 2 weeks.
+            Network::Bellscoin => Params {
+                network: Network::Bellscoin,
+                bip16_time: 1383509530,                 // genesis, active from the start
+                // BIP34/65/66 heights of Bitcoin mainnet kept from upstream. Nothing in this library
+                // validates blocks against them, so they don't affect Bellscoin transactions.
+                bip34_height: 227931,
+                bip65_height: 388381,
+                bip66_height: 363725,
+                rule_change_activation_threshold: 9576, // 95%
+                miner_confirmation_window: 10080,
+                pow_limit: MAX_BITS_BELLSCOIN,
+                pow_target_spacing: 60,                 // 1 minute.
+                pow_target_timespan: 4 * 60 * 60,       // 4 hours.
                 allow_min_difficulty_blocks: false,
                 no_pow_retargeting: false,
             },
             Network::Testnet => Params {
                 network: Network::Testnet,
-                bip16_time: 1333238400,                 // Apr 1 2012
-                bip34_height: 21111, // 0000000023b3a96d3484e5abb3755c413e7d41500f8e2a5c3f0dd01299cd8ef8
-                bip65_height: 581885, // 00000000007f6655f22f98e72ed80d8b06dc761d5da09df0fa1dc4be4f861eb6
-                bip66_height: 330776, // 000000002104c8c45e99a8853285a3b592602a3ccde2b832481da85e9e4ba182
-                rule_change_activation_threshold: 1512, // 75%
-                miner_confirmation_window: 2016,
+                bip16_time: 1391503289,                 // genesis, active from the start
+                // Bitcoin testnet heights kept from upstream, unused like those of mainnet.
+                bip34_height: 21111,
+                bip65_height: 581885,
+                bip66_height: 330776,
+                rule_change_activation_threshold: 7560, // 75%
+                miner_confirmation_window: 10080,
                 pow_limit: MAX_BITS_TESTNET,
-                pow_target_spacing: 10 * 60,            // 10 minutes.
-                pow_target_timespan: 14 * 24 * 60 * 60, // 2 weeks.
+                pow_target_spacing: 60,                 // 1 minute.
+                pow_target_timespan: 4 * 60 * 60,       // 4 hours.
                 allow_min_difficulty_blocks: true,
                 no_pow_retargeting: false,
             },
-            Network::Signet => Params {
-                network: Network::Signet,
-                bip16_time: 1333238400,                 // Apr 1 2012
-                bip34_height: 1,
-                bip65_height: 1,
-                bip66_height: 1,
-                rule_change_activation_threshold: 1916, // 95%
-                miner_confirmation_window: 2016,
-                pow_limit: MAX_BITS_SIGNET,
-                pow_target_spacing: 10 * 60,            // 10 minutes.
-                pow_target_timespan: 14 * 24 * 60 * 60, // 2 weeks.
-                allow_min_difficulty_blocks: false,
-                no_pow_retargeting: false,
-            },
             Network::Regtest => Params {
                 network: Network::Regtest,
                 bip16_time: 1333238400,  // Apr 1 2012
@@ -132,8 +114,8 @@ impl Params {
                 rule_change_activation_threshold: 108, // 75%
                 miner_confirmation_window: 144,
                 pow_limit: MAX_BITS_REGTEST,
-                pow_target_spacing: 10 * 60,            // 10 minutes.
-                pow_target_timespan: 14 * 24 * 60 * 60, // 2 weeks.
+                pow_target_spacing: 60,                 // 1 minute.
+                pow_target_timespan: 4 * 60 * 60,       // 4 hours.
                 allow_min_difficulty_blocks: true,
                 no_pow_retargeting: true,
             },
@@ -1,9 +1,9 @@
 // Written in 2014 by Andrew Poelstra <apoelstra@wpsoftware.net>
 // SPDX-License-Identifier: CC0-1.0
 
-//! Bitcoin network constants.
+//! Bellscoin network constants.
 //!
-//! This module provides various constants relating to the Bitcoin network
+//! This module provides various constants relating to the Bellscoin network
 //! protocol, such as protocol versioning and magic header bytes.
 //!
 //! The [`Network`][1] type implements the [`Decodable`][2] and
@@ -20,10 +20,10 @@
 //! use bitcoin::network::constants::Network;
 //! use bitcoin::consensus::encode::serialize;
 //!
-//! let network = Network::Bitcoin;
+//! let network = Network::Bellscoin;
 //! let bytes = serialize(&network.magic());
 //!
-//! assert_eq!(&bytes[..], &[0xF9, 0xBE, 0xB4, 0xD9]);
+//! assert_eq!(&bytes[..], &[0xC0, 0xC0, 0xC0, 0xC0]);
 //! ```
 
 use core::{fmt, ops, convert::From};
@@ -53,17 +53,19 @@ user_enum! {
     /// The cryptocurrency to act on
     #[derive(Copy, PartialEq, Eq, PartialOrd, Ord, Clone, Hash, Debug)]
     pub enum Network {
-        /// Classic Bitcoin
-        Bitcoin <-> "bitcoin",
-        /// Bitcoin's testnet
+        /// Bellscoin mainnet
+        Bellscoin <-> "bellscoin",
+        /// Bellscoin's testnet
         Testnet <-> "testnet",
-        /// Bitcoin's signet
-        Signet <-> "signet",
-        /// Bitcoin's regtest
+        /// Bellscoin's regtest
         Regtest <-> "regtest"
     }
 }
 
+// Mainnet magic and base58 prefixes are the ones this fork used before testnet and regtest were
+// added. Testnet and regtest magics, ports and WIF prefixes are those of Dogecoin's chainparams,
+// like the testnet address prefixes 113 and 196 the fork already had. WIF prefix 241 replaces
+// Bitcoin's 239 to match them.
 impl Network {
     /// Creates a `Network` from the magic bytes.
     ///
@@ -72,15 +74,14 @@ impl Network {
     /// ```rust
     /// use bitcoin::network::constants::Network;
     ///
-    /// assert_eq!(Some(Network::Bitcoin), Network::from_magic(0xD9B4BEF9));
+    /// assert_eq!(Some(Network::Bellscoin), Network::from_magic(0xC0C0C0C0));
     /// assert_eq!(None, Network::from_magic(0xFFFFFFFF));
     /// ```
     pub fn from_magic(magic: u32) -> Option<Network> {
         // Note: any new entries here must be added to `magic` below
         match magic {
-            0xC0C0C0C0 => Some(Network::Bitcoin),
-            0x0709110B => Some(Network::Testnet),
-            0x40CF030A => Some(Network::Signet),
+            0xC0C0C0C0 => Some(Network::Bellscoin),
+            0xDCB7C1FC => Some(Network::Testnet),
             0xDAB5BFFA => Some(Network::Regtest),
             _ => None
         }
@@ -94,16 +95,59 @@ impl Network {
     /// ```rust
     /// use bitcoin::network::constants::Network;
     ///
-    /// let network = Network::Bitcoin;
-    /// assert_eq!(network.magic(), 0xD9B4BEF9);
+    /// let network = Network::Bellscoin;
+    /// assert_eq!(network.magic(), 0xC0C0C0C0);
     /// ```
     pub fn magic(self) -> u32 {
         // Note: any new entries here must be added to `from_magic` above
         match self {
-            Network::Bitcoin => 0xC0C0C0C0,
-            Network::Testnet => 0x0709110B,
-            Network::Signet  => 0x40CF030A,
-            Network::Regtest => 0xDAB5BFFA,
+            Network::Bellscoin => 0xC0C0C0C0,
+            Network::Testnet   => 0xDCB7C1FC,
+            Network::Regtest   => 0xDAB5BFFA,
+        }
+    }
+
+    /// Default port of peer-to-peer connections.
+    pub fn default_port(self) -> u16 {
+        match self {
+            Network::Bellscoin => 19919,
+            Network::Testnet   => 44556,
+            Network::Regtest   => 18444,
+        }
+    }
+
+    /// Base58 prefix of p2pkh addresses.
+    ///
+    /// Testnet and regtest share base58 prefixes, so legacy addresses and keys decode as testnet.
+    pub fn pubkey_address_prefix(self) -> u8 {
+        match self {
+            Network::Bellscoin => 25,
+            Network::Testnet | Network::Regtest => 113,
+        }
+    }
+
+    /// Base58 prefix of p2sh addresses.
+    pub fn script_address_prefix(self) -> u8 {
+        match self {
+            Network::Bellscoin => 30,
+            Network::Testnet | Network::Regtest => 196,
+        }
+    }
+
+    /// Base58 prefix of WIF private keys.
+    pub fn secret_key_prefix(self) -> u8 {
+        match self {
+            Network::Bellscoin => 153,
+            Network::Testnet | Network::Regtest => 241,
+        }
+    }
+
+    /// Human readable part of bech32 segwit addresses.
+    pub fn bech32_hrp(self) -> &'static str {
+        match self {
+            Network::Bellscoin => "bel",
+            Network::Testnet   => "tbel",
+            Network::Regtest   => "rbel",
         }
     }
 }
@@ -289,30 +333,30 @@ mod tests {
 
     #[test]
     fn serialize_test() {
-        assert_eq!(serialize(&Network::Bitcoin.magic()), &[0xf9, 0xbe, 0xb4, 0xd9]);
-        assert_eq!(serialize(&Network::Testnet.magic()), &[0x0b, 0x11, 0x09, 0x07]);
-        assert_eq!(serialize(&Network::Signet.magic()), &[0x0a, 0x03, 0xcf, 0x40]);
+        assert_eq!(serialize(&Network::Bellscoin.magic()), &[0xc0, 0xc0, 0xc0, 0xc0]);
+        assert_eq!(serialize(&Network::Testnet.magic()), &[0xfc, 0xc1, 0xb7, 0xdc]);
         assert_eq!(serialize(&Network::Regtest.magic()), &[0xfa, 0xbf, 0xb5, 0xda]);
 
-        assert_eq!(deserialize(&[0xf9, 0xbe, 0xb4, 0xd9]).ok(), Some(Network::Bitcoin.magic()));
-        assert_eq!(deserialize(&[0x0b, 0x11, 0x09, 0x07]).ok(), Some(Network::Testnet.magic()));
-        assert_eq!(deserialize(&[0x0a, 0x03, 0xcf, 0x40]).ok(), Some(Network::Signet.magic()));
+        assert_eq!(deserialize(&[0xc0, 0xc0, 0xc0, 0xc0]).ok(), Some(Network::Bellscoin.magic()));
+        assert_eq!(deserialize(&[0xfc, 0xc1, 0xb7, 0xdc]).ok(), Some(Network::Testnet.magic()));
         assert_eq!(deserialize(&[0xfa, 0xbf, 0xb5, 0xda]).ok(), Some(Network::Regtest.magic()));
 
+        for network in [Network::Bellscoin, Network::Testnet, Network::Regtest] {
+            assert_eq!(Network::from_magic(network.magic()), Some(network));
+        }
     }
 
     #[test]
     fn string_test() {
-        assert_eq!(Network::Bitcoin.to_string(), "bitcoin");
+        assert_eq!(Network::Bellscoin.to_string(), "bellscoin");
         assert_eq!(Network::Testnet.to_string(), "testnet");
         assert_eq!(Network::Regtest.to_string(), "regtest");
-        assert_eq!(Network::Signet.to_string(), "signet");
 
-        assert_eq!("bitcoin".parse::<Network>().unwrap(), Network::Bitcoin);
+        assert_eq!("bellscoin".parse::<Network>().unwrap(), Network::Bellscoin);
         assert_eq!("testnet".parse::<Network>().unwrap(), Network::Testnet);
         assert_eq!("regtest".parse::<Network>().unwrap(), Network::Regtest);
-        assert_eq!("signet".parse::<Network>().unwrap(), Network::Signet);
         assert!("fakenet".parse::<Network>().is_err());
+        assert!("signet".parse::<Network>().is_err());
     }
 
     #[test]
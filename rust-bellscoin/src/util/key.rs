@@ -349,10 +349,7 @@ impl PrivateKey {
     /// Format the private key to WIF format.
     pub fn fmt_wif(&self, fmt: &mut dyn fmt::Write) -> fmt::Result {
         let mut ret = [0; 34];
-        ret[0] = match self.network {
-            Network::Bitcoin => 153,
-            Network::Testnet | Network::Signet | Network::Regtest => 239,
-        };
+        ret[0] = self.network.secret_key_prefix();
         ret[1..33].copy_from_slice(&self.inner[..]);
         let privkey = if self.compressed {
             ret[33] = 1;
@@ -384,8 +381,8 @@ impl PrivateKey {
         };
 
         let network = match data[0] {
-            153 => Network::Bitcoin,
-            239 => Network::Testnet,
+            x if x == Network::Bellscoin.secret_key_prefix() => Network::Bellscoin,
+            x if x == Network::Testnet.secret_key_prefix() => Network::Testnet,
             x   => {
                 return Err(Error::Base58(base58::Error::InvalidAddressVersion(x)));
             }
@@ -551,32 +548,32 @@ mod tests {
     use std::str::FromStr;
     use crate::hashes::hex::{FromHex, ToHex};
     use crate::network::constants::Network::Testnet;
-    use crate::network::constants::Network::Bitcoin;
+    use crate::network::constants::Network::Bellscoin;
     use crate::util::address::Address;
 
     #[test]
     fn test_key_derivation() {
         // testnet compressed
-        let sk = PrivateKey::from_wif("cVt4o7BGAig1UXywgGSmARhxMdzP5qvQsxKkSsc1XEkw3tDTQFpy").unwrap();
+        let sk = PrivateKey::from_wif("co3KwRu4X2WHRa1UnREYkmtvrWPgkgTsCqa1ZnJCWCGMcYZ9w8Qb").unwrap();
         assert_eq!(sk.network, Testnet);
         assert!(sk.compressed);
-        assert_eq!(&sk.to_wif(), "cVt4o7BGAig1UXywgGSmARhxMdzP5qvQsxKkSsc1XEkw3tDTQFpy");
+        assert_eq!(&sk.to_wif(), "co3KwRu4X2WHRa1UnREYkmtvrWPgkgTsCqa1ZnJCWCGMcYZ9w8Qb");
 
         let secp = Secp256k1::new();
         let pk = Address::p2pkh(&sk.public_key(&secp), sk.network);
-        assert_eq!(&pk.to_string(), "mqwpxxvfv3QbM8PU8uBx2jaNt9btQqvQNx");
+        assert_eq!(&pk.to_string(), "ned2wBXFLQLLyzfeBjrazz7x9A7muFMKJ6");
 
         // test string conversion
-        assert_eq!(&sk.to_string(), "cVt4o7BGAig1UXywgGSmARhxMdzP5qvQsxKkSsc1XEkw3tDTQFpy");
+        assert_eq!(&sk.to_string(), "co3KwRu4X2WHRa1UnREYkmtvrWPgkgTsCqa1ZnJCWCGMcYZ9w8Qb");
         let sk_str =
-            PrivateKey::from_str("cVt4o7BGAig1UXywgGSmARhxMdzP5qvQsxKkSsc1XEkw3tDTQFpy").unwrap();
+            PrivateKey::from_str("co3KwRu4X2WHRa1UnREYkmtvrWPgkgTsCqa1ZnJCWCGMcYZ9w8Qb").unwrap();
         assert_eq!(&sk.to_wif(), &sk_str.to_wif());
 
         // mainnet uncompressed
-        let sk = PrivateKey::from_wif("5JYkZjmN7PVMjJUfJWfRFwtuXTGB439XV6faajeHPAM9Z2PT2R3").unwrap();
-        assert_eq!(sk.network, Bitcoin);
+        let sk = PrivateKey::from_wif("699MyXzAR2Q26p1Hy1BQ6xtGRJf8JeDRA5SGgUqhEYWqtbd1b9B").unwrap();
+        assert_eq!(sk.network, Bellscoin);
         assert!(!sk.compressed);
-        assert_eq!(&sk.to_wif(), "5JYkZjmN7PVMjJUfJWfRFwtuXTGB439XV6faajeHPAM9Z2PT2R3");
+        assert_eq!(&sk.to_wif(), "699MyXzAR2Q26p1Hy1BQ6xtGRJf8JeDRA5SGgUqhEYWqtbd1b9B");
 
         let secp = Secp256k1::new();
         let mut pk = sk.public_key(&secp);
@@ -584,7 +581,7 @@ mod tests {
         assert_eq!(&pk.to_string(), "042e58afe51f9ed8ad3cc7897f634d881fdbe49a81564629ded8156bebd2ffd1af191923a2964c177f5b5923ae500fca49e99492d534aa3759d6b25a8bc971b133");
         assert_eq!(pk, PublicKey::from_str("042e58afe51f9ed8ad3cc7897f634d881fdbe49a81564629ded8156bebd2ffd1af191923a2964c177f5b5923ae500fca49e99492d534aa3759d6b25a8bc971b133").unwrap());
         let addr = Address::p2pkh(&pk, sk.network);
-        assert_eq!(&addr.to_string(), "1GhQvF6dL8xa6wBxLnWmHcQsurx9RxiMc8");
+        assert_eq!(&addr.to_string(), "BL9VXxYq5eYUYmg7xGqjQkDYeUPjE2rHdU");
         pk.compressed = true;
         assert_eq!(&pk.to_string(), "032e58afe51f9ed8ad3cc7897f634d881fdbe49a81564629ded8156bebd2ffd1af");
         assert_eq!(pk, PublicKey::from_str("032e58afe51f9ed8ad3cc7897f634d881fdbe49a81564629ded8156bebd2ffd1af").unwrap());
@@ -611,7 +608,7 @@ mod tests {
     fn test_key_serde() {
         use serde_test::{Configure, Token, assert_tokens};
 
-        static KEY_WIF: &str = "cVt4o7BGAig1UXywgGSmARhxMdzP5qvQsxKkSsc1XEkw3tDTQFpy";
+        static KEY_WIF: &str = "co3KwRu4X2WHRa1UnREYkmtvrWPgkgTsCqa1ZnJCWCGMcYZ9w8Qb";
         static PK_STR: &str = "039b6347398505f5ec93826dc61c19f47c66c0283ee9be980e29ce325a0f4679ef";
         static PK_STR_U: &str = "\
             04\
@@ -21,6 +21,9 @@ pub use self::message_signing::{MessageSignature, MessageSignatureError};
 /// The prefix for signed messages using Bitcoin's message signing protocol.
 pub const BITCOIN_SIGNED_MSG_PREFIX: &[u8] = b"\x18Bitcoin Signed Message:\n";
 
+/// The prefix for signed messages using Bellscoin's message signing protocol.
+pub const BELLSCOIN_SIGNED_MSG_PREFIX: &[u8] = b"\x1aBellscoin Signed Message:\n";
+
 #[cfg(feature = "secp-recovery")]
 mod message_signing {
     #[cfg(feature = "base64")] use crate::prelude::*;
@@ -248,8 +251,18 @@ pub fn script_find_and_remove(haystack: &mut Vec<u8>, needle: &[u8]) -> usize {
 
 /// Hash message for signature using Bitcoin's message signing format.
 pub fn signed_msg_hash(msg: &str) -> sha256d::Hash {
+    signed_msg_hash_with_prefix(BITCOIN_SIGNED_MSG_PREFIX, msg)
+}
+
+/// Hash message for signature using Bellscoin's message signing format.
+pub fn bellscoin_signed_msg_hash(msg: &str) -> sha256d::Hash {
+    signed_msg_hash_with_prefix(BELLSCOIN_SIGNED_MSG_PREFIX, msg)
+}
+
+/// Hash message for signature after the length-prefixed magic `prefix` of a chain.
+pub fn signed_msg_hash_with_prefix(prefix: &[u8], msg: &str) -> sha256d::Hash {
     let mut engine = sha256d::Hash::engine();
-    engine.input(BITCOIN_SIGNED_MSG_PREFIX);
+    engine.input(prefix);
     let msg_len = encode::VarInt(msg.len() as u64);
     msg_len.consensus_encode(&mut engine).expect("engines don't error");
     engine.input(msg.as_bytes());
@@ -308,6 +321,13 @@ mod tests {
         assert_eq!(hash.to_hex(), "a6f87fe6d58a032c320ff8d1541656f0282c2c7bfcc69d61af4c8e8ed528e49c");
     }
 
+    #[test]
+    fn test_bellscoin_signed_msg_hash() {
+        let hash = bellscoin_signed_msg_hash("test");
+        assert_eq!(hash.to_hex(), "20cf7aa396b965e20cfa1422a3098baf7d2db52b0055c8857884f410e2e0aee1");
+        assert_ne!(hash, signed_msg_hash("test"));
+    }
+
     #[test]
     #[cfg(all(feature = "secp-recovery", feature = "base64"))]
     fn test_message_signature() {
@@ -334,14 +354,14 @@ mod tests {
         assert!(pubkey.compressed);
         assert_eq!(pubkey.inner, secp256k1::PublicKey::from_secret_key(&secp, &privkey));
 
-        let p2pkh = Address::p2pkh(&pubkey, Network::Bitcoin);
+        let p2pkh = Address::p2pkh(&pubkey, Network::Bellscoin);
         assert_eq!(signature2.is_signed_by_address(&secp, &p2pkh, msg_hash), Ok(true));
-        let p2wpkh = Address::p2wpkh(&pubkey, Network::Bitcoin).unwrap();
+        let p2wpkh = Address::p2wpkh(&pubkey, Network::Bellscoin).unwrap();
         assert_eq!(
             signature2.is_signed_by_address(&secp, &p2wpkh, msg_hash),
             Err(MessageSignatureError::UnsupportedAddressType(AddressType::P2wpkh))
         );
-        let p2shwpkh = Address::p2shwpkh(&pubkey, Network::Bitcoin).unwrap();
+        let p2shwpkh = Address::p2shwpkh(&pubkey, Network::Bellscoin).unwrap();
         assert_eq!(
             signature2.is_signed_by_address(&secp, &p2shwpkh, msg_hash),
             Err(MessageSignatureError::UnsupportedAddressType(AddressType::P2sh))
@@ -369,7 +389,7 @@ mod tests {
             &::base64::decode(&pubkey_base64).expect("base64 string")
         ).expect("pubkey slice");
 
-        let p2pkh = Address::p2pkh(&pubkey, Network::Bitcoin);
+        let p2pkh = Address::p2pkh(&pubkey, Network::Bellscoin);
         assert_eq!(signature.is_signed_by_address(&secp, &p2pkh, msg_hash), Ok(false));
     }
 }
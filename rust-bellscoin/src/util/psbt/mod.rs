@@ -344,7 +344,7 @@ mod tests {
 
     use crate::blockdata::script::Script;
     use crate::blockdata::transaction::{Transaction, TxIn, TxOut, OutPoint, Sequence};
-    use crate::network::constants::Network::Bitcoin;
+    use crate::network::constants::Network::Bellscoin;
     use crate::consensus::encode::{deserialize, serialize, serialize_hex};
     use crate::util::bip32::{ChildNumber, ExtendedPrivKey, ExtendedPubKey, KeySource};
     use crate::util::psbt::map::{Output, Input};
@@ -391,7 +391,7 @@ mod tests {
 
         let mut hd_keypaths: BTreeMap<secp256k1::PublicKey, KeySource> = Default::default();
 
-        let mut sk: ExtendedPrivKey = ExtendedPrivKey::new_master(Bitcoin, &seed).unwrap();
+        let mut sk: ExtendedPrivKey = ExtendedPrivKey::new_master(Bellscoin, &seed).unwrap();
 
         let fprint = sk.fingerprint(secp);
 
@@ -0,0 +1,57 @@
+use super::*;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Chain {
+	#[default]
+	Mainnet,
+	Testnet,
+	Regtest,
+}
+
+impl Chain {
+	pub fn network(self) -> Network {
+		match self {
+			Self::Mainnet => Network::Bellscoin,
+			Self::Testnet => Network::Testnet,
+			Self::Regtest => Network::Regtest,
+		}
+	}
+
+	/// Esplora-compatible API used when `--api-url` is not set. Test networks expect
+	/// a local electrs instance on its default HTTP port
+	pub fn default_api_url(self) -> &'static str {
+		match self {
+			Self::Mainnet => "http://bells.quark.blue/api/",
+			Self::Testnet => "http://127.0.0.1:3001/",
+			Self::Regtest => "http://127.0.0.1:3002/",
+		}
+	}
+
+	/// BIP44 coin type of wallet keys. Mainnet keeps 0 used by wallets created before
+	/// networks were supported, test networks use 1 as all BIP44 testnets do
+	pub fn coin_type(self) -> u32 {
+		match self {
+			Self::Mainnet => 0,
+			Self::Testnet | Self::Regtest => 1,
+		}
+	}
+
+	/// Fail if `address` can't be used on this chain
+	pub fn check_address(self, address: &Address) -> Result {
+		if !address.is_valid_for_network(self.network()) {
+			bail!("Address {address} is not valid for {self}");
+		}
+		Ok(())
+	}
+}
+
+impl Display for Chain {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(f, "{}", match self {
+			Self::Mainnet => "mainnet",
+			Self::Testnet => "testnet",
+			Self::Regtest => "regtest",
+		})
+	}
+}
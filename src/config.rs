@@ -1,45 +1,32 @@
-// use super::*;
+use {super::*, minter::{audit::Redaction, backend::BackendKind}};
 
-// use serde::{Serialize, Deserialize};
-// use serde_yaml;
-// use std::fs::File;
-// use std::io::Write;
+/// Name of config file looked up in data dir when `--config` is not set
+pub(crate) const CONFIG_FILE: &str = "bells.yaml";
 
-// // Define your data structure
-// #[derive(Serialize, Deserialize)]
-// pub(crate) struct WalletConfig {
-//     mnemonic: String,
-//     passphrase: Option<String>,
-//     addresses: Vec<String>,
-//     utxos: Vec<u128>,    
-//     inscriptions: Vec<u128>,    
-// }
+/// Settings of `bells.yaml`. Command line options and environment variables take precedence
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+	pub(crate) api_url: Option<String>,
+	pub(crate) network: Option<Chain>,
+	pub(crate) wallet: Option<String>,
+	/// Fee rate in nook/vB used when `--fee-rate` is not set
+	pub(crate) fee_rate: Option<f64>,
+	pub(crate) log_level: Option<String>,
+	pub(crate) backend: Option<BackendKind>,
+	pub(crate) rpc_url: Option<String>,
+	pub(crate) rpc_user: Option<String>,
+	pub(crate) rpc_password: Option<String>,
+	pub(crate) rpc_cookie: Option<PathBuf>,
+	pub(crate) offline: Option<bool>,
+	pub(crate) redact: Option<Redaction>,
+	/// File secrets are appended to with `redact: none`
+	pub(crate) secrets_file: Option<PathBuf>,
+}
 
-// impl WalletConfig {    
-    
-//     pub(crate) fn new(mnemonic: String, passphrase: Option<String>) -> Self {
-//         Self {
-//             mnemonic,
-//             passphrase,
-//             addresses: Vec::new(),
-//             utxos: Vec::new(),
-//             inscriptions: Vec::new(),
-//         }
-//     }
-
-//     pub(crate) fn save(wallet: WalletConfig, options: &Options) -> Result<Self> {
-//         let mut path = PathBuf::from(&options.wallet);
-//         path.set_extension("yaml");    
-//         if path.exists() {
-//            bail!("Wallet file already exists.");
-//         } else {
-//             let yaml_data = serde_yaml::to_string(&wallet)?;
-        
-//             let mut file = File::create(&path).expect("Unable to create file");
-//             file.write_all(yaml_data.as_bytes()).expect("Unable to write data");
-
-//             Ok(wallet)
-//         }
-//     }
-
-// }
+impl Config {
+	pub(crate) fn load(path: &Path) -> Result<Self> {
+		let file = File::open(path).with_context(|| format!("Failed to open config {}", path.display()))?;
+		serde_yaml::from_reader(file).with_context(|| format!("Invalid config {}", path.display()))
+	}
+}
@@ -4,31 +4,24 @@ use anyhow::Context;
 use tracing::{info, trace};
 
 pub struct Database {
-    pub db: rocksdb::DB,
+    db: rocksdb::DB,
 }
 
 pub type DbTable<'a> = rocksdb::ColumnFamilyRef<'a>;
 
+/// Handle of column family keeping its DB open. Column families are only dropped with
+/// `Database::recreate_column_family`, which needs the only handle of DB, so table exists while its handle exists
 #[derive(Clone)]
 pub struct OwnedDbTable {
     name: String,
-    table: DbTable<'static>, // 🤡
-    _owner: Arc<Database>,
+    db: Arc<Database>,
 }
 impl OwnedDbTable {
-    pub fn from_bounded(db: Arc<Database>, table: DbTable, name: String) -> Self {
-        Self {
-            name,
-            table: unsafe { std::mem::transmute::<_,DbTable<'static>>(table) },
-            _owner: db,
-        }
-    }
-
     pub fn name(&self) -> &str { &self.name }
-    pub fn table(&self) -> &DbTable { &self.table }
+    pub fn table(&self) -> anyhow::Result<DbTable<'_>> {
+        self.db.db.cf_handle(&self.name).with_context(|| format!("Column family {} not found", self.name))
+    }
 }
-unsafe impl Send for OwnedDbTable {}
-unsafe impl Sync for OwnedDbTable {}
 
 impl Database {
     pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Arc<Database>> {
@@ -42,6 +35,11 @@ impl Database {
         cf.push("utxo".to_owned());
         cf.push("wallets".to_owned());
         cf.push("addresses".to_owned());
+        cf.push("frozen".to_owned());
+        cf.push("audit".to_owned());
+        cf.push("indices".to_owned());
+        cf.push("transactions".to_owned());
+        cf.push("meta".to_owned());
 
         let mut opt = rocksdb::Options::default();
         opt.create_if_missing(true);
@@ -57,11 +55,14 @@ impl Database {
         Ok(self.db.cf_handle(name).unwrap())
     }
     pub fn owned_column_family(self: &Arc<Self>, name: &str) -> anyhow::Result<OwnedDbTable> {
-        Ok(OwnedDbTable::from_bounded(self.clone(), self.column_family(name)?, name.to_owned()))
+        self.column_family(name)?;
+        Ok(OwnedDbTable { name: name.to_owned(), db: self.clone() })
     }
-    pub fn recreate_column_family(&self, name: &str) -> anyhow::Result<()> {
-        self.db.drop_cf(name).context("Failed to drop column family")?;
-        self.db.create_cf(name, &rocksdb::Options::default()).context("Failed to create column family for DB")
+    /// Drop and create column family again. Fails if there are other handles of DB, including table handles
+    pub fn recreate_column_family(self: &mut Arc<Self>, name: &str) -> anyhow::Result<()> {
+        let this = Arc::get_mut(self).context("Can't recreate column family while DB is in use")?;
+        this.db.drop_cf(name).context("Failed to drop column family")?;
+        this.db.create_cf(name, &rocksdb::Options::default()).context("Failed to create column family for DB")
     }
 
     pub fn set_raw(&self, f: &DbTable, k: impl AsRef<[u8]>, v: impl AsRef<[u8]>) -> anyhow::Result<()> {
@@ -83,6 +84,51 @@ impl Database {
         self.db.write(batch).context("Failed to delete rows")
     }
 
+    /// Put `put` rows and delete `delete` keys in one atomic batch
+    pub fn write_many(&self, f: &DbTable, put: impl IntoIterator<Item = (impl AsRef<[u8]> + Debug, impl serde::Serialize)>, delete: impl IntoIterator<Item = impl AsRef<[u8]> + Debug>) -> anyhow::Result<()> {
+        let mut batch = rocksdb::WriteBatch::default();
+        for k in delete.into_iter() {
+            trace!("db batch remove at {k:?}");
+            batch.delete_cf(f, k);
+        }
+        for (k,v) in put.into_iter() {
+            trace!("db batch set at {k:?}");
+            let v = bincode::serialize(&v).context("Failed to serialize val")?;
+            batch.put_cf(f, k, v);
+        }
+        self.db.write(batch).context("Failed to write rows")
+    }
+
+    pub fn write_batch(&self, batch: rocksdb::WriteBatch) -> anyhow::Result<()> {
+        self.db.write(batch).context("Failed to write batch")
+    }
+
+    /// Add `v` at `k` of `f` to `batch`, so rows of several tables are written atomically with `write_batch`
+    pub fn batch_set(batch: &mut rocksdb::WriteBatch, f: &DbTable, k: impl AsRef<[u8]> + Debug, v: &impl serde::Serialize) -> anyhow::Result<()> {
+        trace!("db batch set at {k:?}");
+        let v = bincode::serialize(v).context("Failed to serialize val")?;
+        batch.put_cf(f, k, v);
+        Ok(())
+    }
+
+    /// Rewrite `tables` on disk, so overwritten and deleted values of them are not left in WAL and SST files.
+    /// WAL file is removed only when every column family is flushed, so all of them are flushed.
+    /// Removed files are unlinked, not wiped, so filesystem may keep their blocks until they are reused
+    pub fn purge_stale(&self, tables: &[&DbTable]) -> anyhow::Result<()> {
+        for name in rocksdb::DB::list_cf(&rocksdb::Options::default(), self.db.path()).context("Failed to list column families")? {
+            let Some(cf) = self.db.cf_handle(&name) else { continue };
+            self.db.flush_cf(&cf).with_context(|| format!("Failed to flush {name}"))?;
+        }
+        self.db.flush_wal(true).context("Failed to flush DB log")?;
+
+        let mut opt = rocksdb::CompactOptions::default();
+        opt.set_bottommost_level_compaction(rocksdb::BottommostLevelCompaction::Force);
+        for f in tables {
+            self.db.compact_range_cf_opt(*f, None::<&[u8]>, None::<&[u8]>, &opt);
+        }
+        Ok(())
+    }
+
     pub fn get_raw(&self, f: &DbTable, k: impl AsRef<[u8]>) -> anyhow::Result<Option<Vec<u8>>> {
         self.db.get_cf(f, k).context("Failed to get value from DB")
     }
@@ -160,3 +206,23 @@ impl<'a> Iterator for ScanIterator<'a> {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_family_is_not_recreated_while_table_handle_exists() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = Database::open(dir.path().join("db")).unwrap();
+        let table = db.owned_column_family("utxo").unwrap();
+        db.set_raw(&table.table().unwrap(), "key", "value").unwrap();
+
+        assert!(db.recreate_column_family("utxo").is_err());
+        assert_eq!(db.get_raw(&table.table().unwrap(), "key").unwrap(), Some(b"value".to_vec()));
+
+        drop(table);
+        db.recreate_column_family("utxo").unwrap();
+        assert_eq!(db.get_raw(&db.column_family("utxo").unwrap(), "key").unwrap(), None);
+    }
+}
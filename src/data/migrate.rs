@@ -0,0 +1,333 @@
+use anyhow::{bail, Context};
+use bitcoin::secp256k1::SecretKey;
+use serde::Serialize;
+
+use crate::{chain::Chain, minter::utxo::{Status, UtxoData}, wallet::{AddressType, Wallet, WalletAddressData}};
+
+use super::db::Database;
+
+/// Schema version of DB written by this build
+pub const SCHEMA_VERSION: u32 = 3;
+
+const META_TABLE: &str = "meta";
+const VERSION_KEY: &[u8] = b"schema_version";
+
+/// Step upgrading DB from `version - 1` to `version`
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    run: fn(&Database, &mut rocksdb::WriteBatch) -> anyhow::Result<MigrationReport>,
+}
+
+/// Rows changed by migration
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct MigrationReport {
+    /// Rows rewritten in current layout
+    pub converted: usize,
+    /// Undecodable rows of cached chain data, which will be fetched again
+    pub removed: usize,
+    /// Undecodable rows left untouched
+    pub invalid: usize,
+}
+
+/// Migrations in order of versions. DB without stored version has version 0
+pub static MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, description: "Convert wallets saved before encryption and network support", run: migrate_wallets },
+    Migration { version: 2, description: "Convert addresses saved before encryption and derivation path support", run: migrate_addresses },
+    Migration { version: 3, description: "Convert utxo's saved before inscription support, remove undecodable ones", run: migrate_utxo },
+];
+
+impl Database {
+    /// Schema version of DB. 0 if it was never set
+    pub fn schema_version(&self) -> anyhow::Result<u32> {
+        Ok(self.get::<u32>(&self.column_family(META_TABLE)?, VERSION_KEY).context("Failed to get schema version")?.unwrap_or(0))
+    }
+
+    /// Migrations which are not applied to DB yet. Fails if DB was written by newer build
+    pub fn pending_migrations(&self) -> anyhow::Result<impl Iterator<Item = &'static Migration>> {
+        let version = self.schema_version()?;
+        if version > SCHEMA_VERSION {
+            bail!("DB has schema version {version}, but this build supports up to {SCHEMA_VERSION}. Upgrade minter");
+        }
+        Ok(MIGRATIONS.iter().filter(move |x| x.version > version))
+    }
+
+    /// Run pending migrations in order. Every migration is written with its version in one batch,
+    /// so failed migration leaves DB at previous version. Nothing is written if `dry_run`
+    pub fn migrate(&self, dry_run: bool) -> anyhow::Result<Vec<(&'static Migration, MigrationReport)>> {
+        let mut applied = vec![];
+        for migration in self.pending_migrations()? {
+            let mut batch = rocksdb::WriteBatch::default();
+            let report = (migration.run)(self, &mut batch).with_context(|| format!("Failed to run migration {}", migration.version))?;
+            if !dry_run {
+                let version = bincode::serialize(&migration.version).context("Failed to serialize schema version")?;
+                batch.put_cf(&self.column_family(META_TABLE)?, VERSION_KEY, version);
+                self.write_batch(batch).with_context(|| format!("Failed to write migration {}", migration.version))?;
+                info!("Migrated DB to schema version {}: {report:?}", migration.version);
+            }
+            applied.push((migration, report));
+        }
+        Ok(applied)
+    }
+}
+
+/// Decoder of row saved in layout of previous schema version
+type LegacyDecoder<T> = fn(&[u8]) -> Option<T>;
+
+/// Rewrite every row of `table` from layout of previous schema version to `T`. Only one migration changes layout
+/// of a table, so its rows have layout of version 0 until it runs. Layout is never guessed from which decoder
+/// accepts row: bincode accepts trailing bytes, so row of one layout may decode as garbage of another.
+/// Rows rejected by `decode` are removed if `remove_invalid`
+fn convert_rows<T: Serialize>(
+    db: &Database,
+    batch: &mut rocksdb::WriteBatch,
+    table: &str,
+    decode: LegacyDecoder<T>,
+    remove_invalid: bool,
+) -> anyhow::Result<MigrationReport> {
+    let cf = db.column_family(table)?;
+    let mut report = MigrationReport::default();
+    for (k,v) in db.iterate(&cf, vec![]).with_context(|| format!("Failed to iterate {table}"))? {
+        match decode(&v) {
+            Some(row) => {
+                batch.put_cf(&cf, &k, bincode::serialize(&row).context("Failed to serialize row")?);
+                report.converted += 1;
+            }
+            None if remove_invalid => {
+                batch.delete_cf(&cf, &k);
+                report.removed += 1;
+            }
+            None => {
+                warn!("Row {} of {table} has unknown layout. Keeping it", String::from_utf8_lossy(&k));
+                report.invalid += 1;
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[derive(serde::Deserialize)]
+struct LegacyWallet {
+    name: String,
+    mnemonic: String,
+    passphrase: Option<String>,
+}
+
+// wallets saved before network support are mainnet wallets
+fn migrate_wallets(db: &Database, batch: &mut rocksdb::WriteBatch) -> anyhow::Result<MigrationReport> {
+    convert_rows::<Wallet>(db, batch, "wallets",
+        |v| bincode::deserialize::<LegacyWallet>(v).ok().map(|x| Wallet::new(x.mnemonic, x.passphrase, x.name, Chain::Mainnet)),
+        false)
+}
+
+#[derive(serde::Deserialize)]
+struct LegacyWalletAddressData {
+    private: Option<SecretKey>,
+    ty: AddressType,
+}
+
+fn migrate_addresses(db: &Database, batch: &mut rocksdb::WriteBatch) -> anyhow::Result<MigrationReport> {
+    convert_rows::<WalletAddressData>(db, batch, "addresses",
+        |v| bincode::deserialize::<LegacyWalletAddressData>(v).ok().map(|x| WalletAddressData::new(x.private, x.ty)),
+        false)
+}
+
+#[derive(serde::Deserialize)]
+struct LegacyUtxoData {
+    txid: bitcoin::Txid,
+    vout: u32,
+    status: Status,
+    value: u64,
+    ty: AddressType,
+}
+
+// utxo's are cache of chain data, so rows of unknown layout are fetched again.
+// Builds without schema version saved them with or without fields added for inscriptions,
+// which are told apart by bytes left after fields of older layout
+fn migrate_utxo(db: &Database, batch: &mut rocksdb::WriteBatch) -> anyhow::Result<MigrationReport> {
+    convert_rows::<UtxoData>(db, batch, "utxo", |v| {
+        let mut rest = v;
+        let x = bincode::deserialize_from::<_, LegacyUtxoData>(&mut rest).ok()?;
+        if !rest.is_empty() {
+            return bincode::deserialize::<UtxoData>(v).ok();
+        }
+        Some(UtxoData {
+            txid: x.txid,
+            vout: x.vout,
+            status: x.status,
+            value: x.value,
+            ty: x.ty,
+            inscription_meta: None,
+            owner: None,
+        })
+    }, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::hashes::Hash;
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+    use crate::wallet::WalletSecrets;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    // layouts written by older builds
+
+    #[derive(serde::Serialize)]
+    struct OldWallet<'a> {
+        name: &'a str,
+        mnemonic: &'a str,
+        passphrase: Option<&'a str>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct OldAddress {
+        private: Option<SecretKey>,
+        ty: AddressType,
+    }
+
+    #[derive(serde::Serialize)]
+    struct OldUtxo {
+        txid: bitcoin::Txid,
+        vout: u32,
+        status: Status,
+        value: u64,
+        ty: AddressType,
+    }
+
+    fn open() -> (tempfile::TempDir, std::sync::Arc<Database>) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(dir.path().join("db")).unwrap();
+        (dir, db)
+    }
+
+    fn put(db: &Database, table: &str, key: &str, row: &impl Serialize) {
+        db.set(&db.column_family(table).unwrap(), key, row).unwrap();
+    }
+
+    fn get<T: DeserializeOwned>(db: &Database, table: &str, key: &str) -> Option<T> {
+        db.get(&db.column_family(table).unwrap(), key).unwrap()
+    }
+
+    fn private() -> SecretKey {
+        SecretKey::from_slice(&[1; 32]).unwrap()
+    }
+
+    fn status() -> Status {
+        Status { confirmed: true, block_height: Some(10), block_hash: None, block_time: None }
+    }
+
+    #[test]
+    fn legacy_rows_are_converted() {
+        let (_dir, db) = open();
+        put(&db, "wallets", "old", &OldWallet { name: "old", mnemonic: MNEMONIC, passphrase: Some("pass") });
+        put(&db, "wallets", "nopass", &OldWallet { name: "nopass", mnemonic: MNEMONIC, passphrase: None });
+        put(&db, "addresses", "old/a", &OldAddress { private: Some(private()), ty: AddressType::Ord });
+        put(&db, "addresses", "old/b", &OldAddress { private: None, ty: AddressType::Utxo });
+        put(&db, "utxo", "old/a/tx:0", &OldUtxo { txid: bitcoin::Txid::all_zeros(), vout: 3, status: status(), value: 5000, ty: AddressType::Ord });
+        let utxo = UtxoData {
+            txid: bitcoin::Txid::all_zeros(),
+            vout: 4,
+            status: status(),
+            value: 6000,
+            ty: AddressType::Utxo,
+            inscription_meta: None,
+            owner: Some("owner".to_owned()),
+        };
+        put(&db, "utxo", "old/a/tx:4", &utxo);
+
+        let applied = db.migrate(false).unwrap();
+        let converted = applied.iter().map(|(m, r)| (m.version, r.converted, r.removed, r.invalid)).collect::<Vec<_>>();
+        assert_eq!(converted, vec![(1, 2, 0, 0), (2, 2, 0, 0), (3, 2, 0, 0)]);
+        assert_eq!(db.schema_version().unwrap(), SCHEMA_VERSION);
+
+        let wallet = get::<Wallet>(&db, "wallets", "old").unwrap();
+        assert_eq!((wallet.name.as_str(), wallet.network), ("old", Chain::Mainnet));
+        assert!(matches!(wallet.secrets, WalletSecrets::Plain { mnemonic, passphrase } if mnemonic == MNEMONIC && passphrase.as_deref() == Some("pass")));
+        let wallet = get::<Wallet>(&db, "wallets", "nopass").unwrap();
+        assert_eq!(wallet.network, Chain::Mainnet);
+        assert!(matches!(wallet.secrets, WalletSecrets::Plain { passphrase: None, .. }));
+
+        let address = get::<WalletAddressData>(&db, "addresses", "old/a").unwrap();
+        assert_eq!((address.private, address.ty), (Some(private()), AddressType::Ord));
+        assert!(address.encrypted_private.is_none() && address.derivation.is_none());
+        let address = get::<WalletAddressData>(&db, "addresses", "old/b").unwrap();
+        assert_eq!((address.private, address.ty), (None, AddressType::Utxo));
+
+        let utxo = get::<UtxoData>(&db, "utxo", "old/a/tx:0").unwrap();
+        assert_eq!((utxo.vout, utxo.value, utxo.ty), (3, 5000, AddressType::Ord));
+        assert!(utxo.status.confirmed && utxo.inscription_meta.is_none() && utxo.owner.is_none());
+        let utxo = get::<UtxoData>(&db, "utxo", "old/a/tx:4").unwrap();
+        assert_eq!((utxo.vout, utxo.value, utxo.owner.as_deref()), (4, 6000, Some("owner")));
+
+        assert!(db.migrate(false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn layout_is_chosen_by_schema_version() {
+        let (_dir, db) = open();
+        // length of mnemonic reads as tag of watch-only secrets without xpub and its first byte as mainnet tag
+        let old = OldWallet { name: "old", mnemonic: "\0\0", passphrase: Some("pass") };
+        let bytes = bincode::serialize(&old).unwrap();
+        let garbage = bincode::deserialize::<Wallet>(&bytes).unwrap();
+        assert!(matches!(garbage.secrets, WalletSecrets::WatchOnly { xpub: None }));
+        put(&db, "wallets", "old", &old);
+
+        db.migrate(false).unwrap();
+        let wallet = get::<Wallet>(&db, "wallets", "old").unwrap();
+        assert!(matches!(wallet.secrets, WalletSecrets::Plain { mnemonic, passphrase } if mnemonic == "\0\0" && passphrase.as_deref() == Some("pass")));
+    }
+
+    #[test]
+    fn rows_of_applied_migrations_are_not_converted_again() {
+        let (_dir, db) = open();
+        put(&db, META_TABLE, "schema_version", &1u32);
+        put(&db, "wallets", "current", &Wallet::new(MNEMONIC.to_owned(), None, "current".to_owned(), Chain::Regtest));
+        put(&db, "addresses", "old/a", &OldAddress { private: Some(private()), ty: AddressType::Ord });
+
+        let applied = db.migrate(false).unwrap();
+        let versions = applied.iter().map(|(m, r)| (m.version, r.converted)).collect::<Vec<_>>();
+        assert_eq!(versions, vec![(2, 1), (3, 0)]);
+        assert_eq!(get::<Wallet>(&db, "wallets", "current").unwrap().network, Chain::Regtest);
+        assert_eq!(get::<WalletAddressData>(&db, "addresses", "old/a").unwrap().private, Some(private()));
+    }
+
+    #[test]
+    fn undecodable_rows_are_only_removed_from_utxo_cache() {
+        let (_dir, db) = open();
+        for table in ["wallets", "addresses", "utxo"] {
+            db.set_raw(&db.column_family(table).unwrap(), "bad", [0xff]).unwrap();
+        }
+
+        let applied = db.migrate(false).unwrap();
+        let reports = applied.iter().map(|(m, r)| (m.version, r.converted, r.removed, r.invalid)).collect::<Vec<_>>();
+        assert_eq!(reports, vec![(1, 0, 0, 1), (2, 0, 0, 1), (3, 0, 1, 0)]);
+
+        let raw = |table| db.get_raw(&db.column_family(table).unwrap(), "bad").unwrap();
+        assert_eq!(raw("wallets"), Some(vec![0xff]));
+        assert_eq!(raw("addresses"), Some(vec![0xff]));
+        assert_eq!(raw("utxo"), None);
+    }
+
+    #[test]
+    fn dry_run_writes_nothing() {
+        let (_dir, db) = open();
+        put(&db, "wallets", "old", &OldWallet { name: "old", mnemonic: MNEMONIC, passphrase: None });
+
+        let applied = db.migrate(true).unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert_eq!(applied[0].1.converted, 1);
+        assert_eq!(db.schema_version().unwrap(), 0);
+        assert!(db.get::<Wallet>(&db.column_family("wallets").unwrap(), "old").is_err());
+    }
+
+    #[test]
+    fn newer_schema_is_refused() {
+        let (_dir, db) = open();
+        put(&db, META_TABLE, "schema_version", &(SCHEMA_VERSION + 1));
+        assert!(db.pending_migrations().is_err());
+        assert!(db.migrate(false).is_err());
+    }
+}
@@ -3,11 +3,16 @@ use std::sync::Arc;
 use self::db::{Database, OwnedDbTable};
 
 pub mod db;
+pub mod migrate;
 
 pub struct MinterDbTables {
     pub wallets: OwnedDbTable,
     pub addresses: OwnedDbTable,
     pub utxo: OwnedDbTable,
+    pub frozen: OwnedDbTable,
+    pub audit: OwnedDbTable,
+    pub indices: OwnedDbTable,
+    pub transactions: OwnedDbTable,
 }
 
 impl MinterDbTables {
@@ -16,6 +21,10 @@ impl MinterDbTables {
             wallets: db.owned_column_family("wallets")?,
             addresses: db.owned_column_family("addresses")?,
             utxo: db.owned_column_family("utxo")?,
+            frozen: db.owned_column_family("frozen")?,
+            audit: db.owned_column_family("audit")?,
+            indices: db.owned_column_family("indices")?,
+            transactions: db.owned_column_family("transactions")?,
         })
     }
 }
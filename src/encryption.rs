@@ -0,0 +1,142 @@
+use anyhow::{anyhow, Context};
+use bitcoin::secp256k1::rand::{thread_rng, RngCore};
+use chacha20poly1305::{aead::{Aead, Payload}, ChaCha20Poly1305, Key, KeyInit, Nonce};
+use zeroize::Zeroize;
+
+/// Argon2id parameters used to derive wallet key from password
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KdfParams {
+	pub salt: [u8; 16],
+	/// Memory in KiB
+	pub m_cost: u32,
+	pub t_cost: u32,
+	pub p_cost: u32,
+}
+
+impl KdfParams {
+	pub fn new_random() -> Self {
+		let mut salt = [0; 16];
+		thread_rng().fill_bytes(&mut salt);
+		Self {
+			salt,
+			m_cost: 64 * 1024,
+			t_cost: 3,
+			p_cost: 1,
+		}
+	}
+
+	pub fn derive(&self, password: &str) -> anyhow::Result<WalletKey> {
+		let params = argon2::Params::new(self.m_cost, self.t_cost, self.p_cost, Some(32))
+			.map_err(|e| anyhow!("Invalid KDF params: {e}"))?;
+		let argon = argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
+
+		let mut key = [0; 32];
+		argon.hash_password_into(password.as_bytes(), &self.salt, &mut key)
+			.map_err(|e| anyhow!("Failed to derive key from password: {e}"))?;
+		Ok(WalletKey(key))
+	}
+}
+
+/// Data encrypted with ChaCha20-Poly1305
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct EncryptedData {
+	pub nonce: [u8; 12],
+	pub ciphertext: Vec<u8>,
+}
+
+/// Symmetric key derived from wallet password. Zeroed on drop
+pub struct WalletKey([u8; 32]);
+
+impl WalletKey {
+	/// Encrypt `plaintext`. `aad` is not encrypted, but must be the same to decrypt
+	pub fn encrypt(&self, plaintext: &[u8], aad: &[u8]) -> anyhow::Result<EncryptedData> {
+		let mut nonce = [0; 12];
+		thread_rng().fill_bytes(&mut nonce);
+
+		let ciphertext = self.cipher()
+			.encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad })
+			.map_err(|_| anyhow!("Failed to encrypt data"))?;
+		Ok(EncryptedData { nonce, ciphertext })
+	}
+
+	pub fn decrypt(&self, data: &EncryptedData, aad: &[u8]) -> anyhow::Result<Vec<u8>> {
+		self.cipher()
+			.decrypt(Nonce::from_slice(&data.nonce), Payload { msg: &data.ciphertext, aad })
+			.map_err(|_| anyhow!("Failed to decrypt data. Wrong password?"))
+	}
+
+	pub fn encrypt_value(&self, value: &impl serde::Serialize, aad: &[u8]) -> anyhow::Result<EncryptedData> {
+		let mut plaintext = bincode::serialize(value).context("Failed to serialize secret")?;
+		let data = self.encrypt(&plaintext, aad);
+		plaintext.zeroize();
+		data
+	}
+
+	pub fn decrypt_value<T: for<'a> serde::Deserialize<'a>>(&self, data: &EncryptedData, aad: &[u8]) -> anyhow::Result<T> {
+		let mut plaintext = self.decrypt(data, aad)?;
+		let value = bincode::deserialize(&plaintext).context("Invalid decrypted secret");
+		plaintext.zeroize();
+		value
+	}
+
+	fn cipher(&self) -> ChaCha20Poly1305 {
+		ChaCha20Poly1305::new(Key::from_slice(&self.0))
+	}
+}
+
+impl Drop for WalletKey {
+	fn drop(&mut self) {
+		self.0.zeroize();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Minimal Argon2 cost, so tests don't spend seconds on every key
+	fn cheap_params() -> KdfParams {
+		KdfParams { m_cost: 8, t_cost: 1, ..KdfParams::new_random() }
+	}
+
+	#[test]
+	fn value_round_trips_with_key_derived_again() {
+		let kdf = cheap_params();
+		let secrets = ("mnemonic words".to_owned(), Some("passphrase".to_owned()));
+		let data = kdf.derive("password").unwrap().encrypt_value(&secrets, b"wallet").unwrap();
+
+		let decrypted: (String, Option<String>) = kdf.derive("password").unwrap().decrypt_value(&data, b"wallet").unwrap();
+		assert_eq!(decrypted, secrets);
+	}
+
+	#[test]
+	fn wrong_password_is_rejected() {
+		let kdf = cheap_params();
+		let data = kdf.derive("password").unwrap().encrypt_value(&42u64, b"wallet").unwrap();
+		assert!(kdf.derive("Password").unwrap().decrypt_value::<u64>(&data, b"wallet").is_err());
+	}
+
+	#[test]
+	fn other_salt_gives_other_key() {
+		let data = cheap_params().derive("password").unwrap().encrypt_value(&42u64, b"wallet").unwrap();
+		assert!(cheap_params().derive("password").unwrap().decrypt_value::<u64>(&data, b"wallet").is_err());
+	}
+
+	#[test]
+	fn ciphertext_moved_to_other_address_is_rejected() {
+		let key = cheap_params().derive("password").unwrap();
+		let data = key.encrypt_value(&[7u8; 32], b"wallet/address1").unwrap();
+
+		assert_eq!(key.decrypt_value::<[u8; 32]>(&data, b"wallet/address1").unwrap(), [7; 32]);
+		assert!(key.decrypt_value::<[u8; 32]>(&data, b"wallet/address2").is_err());
+		assert!(key.decrypt_value::<[u8; 32]>(&data, b"other/address1").is_err());
+	}
+
+	#[test]
+	fn modified_ciphertext_is_rejected() {
+		let key = cheap_params().derive("password").unwrap();
+		let mut data = key.encrypt(b"secret", b"wallet").unwrap();
+		data.ciphertext[0] ^= 1;
+		assert!(key.decrypt(&data, b"wallet").is_err());
+	}
+}
@@ -6,6 +6,7 @@ use minter::Minter;
 use {
 		self::{
 			deserialize_from_str::DeserializeFromStr,
+			chain::Chain,
 			inscription_id::InscriptionId,
 			outgoing::Outgoing,
 			arguments::Arguments,
@@ -57,7 +58,9 @@ pub use crate::{
   
 
 mod arguments;
+mod chain;
 mod wallet;
+mod encryption;
 mod config;
 mod deserialize_from_str;
 mod outgoing;
@@ -160,12 +163,18 @@ fn quick_test() {
 	
 #[tokio::main]
 pub async fn main() {
+	let mut args = Arguments::parse();
+	if let Err(err) = args.options.resolve() {
+		eprintln!("error: {err:#}");
+		process::exit(1);
+	}
+
 	{
 		use tracing_subscriber::layer::SubscriberExt;
 		use tracing_subscriber::util::SubscriberInitExt;
 		use tracing_subscriber::*;
 		let indicatif_layer = tracing_indicatif::IndicatifLayer::new();
-		let verbosity = "debug";
+		let verbosity = args.options.log_level.as_str();
 		let fmt_layer_a = fmt::layer()
 				.with_writer(indicatif_layer.get_stderr_writer())
 				.with_filter(EnvFilter::new(verbosity));
@@ -190,9 +199,26 @@ pub async fn main() {
 	})
 	.expect("Error setting ctrl-c handler");
 
-	let db_path = "./db";
-	let args = Arguments::parse();
-	let minter = Minter::new(db_path, args.options.api_url.clone()).unwrap();
+	if let Err(err) = fs::create_dir_all(&args.options.data_dir) {
+		eprintln!("error: Failed to create data dir {}: {err}", args.options.data_dir.display());
+		process::exit(1);
+	}
+	let db_path = args.options.data_dir.join("db");
+	let backend = match args.options.chain_backend() {
+		Ok(backend) => backend,
+		Err(err) => {
+			eprintln!("error: {err:#}");
+			process::exit(1);
+		}
+	};
+	let migrate = args.subcommand.migrates_db();
+	let minter = match Minter::new(&db_path, backend, args.options.network, args.options.redact, args.options.secrets_file.clone(), migrate) {
+		Ok(minter) => minter,
+		Err(err) => {
+			eprintln!("error: {err:#}");
+			process::exit(1);
+		}
+	};
 	
 	if let Err(err) = args.run(minter).await {
 		eprintln!("error: {err}");
@@ -0,0 +1,158 @@
+use std::{fmt::Display, io::Write, path::Path, str::FromStr};
+
+use anyhow::Context;
+
+use super::Minter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditAction {
+    CreateWallet,
+    AddAddress,
+    RemoveAddress,
+    Send,
+    SendInscription,
+    Inscribe,
+    Freeze,
+    Unfreeze,
+    Encrypt,
+    Decrypt,
+    CreatePsbt,
+    SignPsbt,
+    Broadcast,
+    SignMessage,
+    BumpFee,
+}
+
+/// What is kept out of audit log and secrets file
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Redaction {
+    /// Created mnemonics, passphrases and private keys are appended to secrets file
+    None,
+    /// Secrets are never saved in plain text
+    #[default]
+    Secrets,
+    /// Secrets are never saved and addresses and public keys in audit log are replaced with `<redacted>`
+    Addresses,
+}
+
+/// Record of action done with wallet. Never contains secrets
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AuditEntry {
+    /// Unix time in milliseconds
+    pub timestamp: i64,
+    pub wallet: String,
+    pub action: AuditAction,
+    pub txid: Option<bitcoin::Txid>,
+    pub details: String,
+}
+
+/// Line of secrets backup file
+#[derive(Debug, serde::Serialize)]
+struct SecretBackup<'a> {
+    time: String,
+    wallet: &'a str,
+    kind: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    address: Option<&'a str>,
+    secret: &'a str,
+}
+
+// keys are ordered by time inside of wallet prefix
+fn audit_key(wallet: &str, time: chrono::DateTime<chrono::Utc>) -> Vec<u8> {
+    let nanos = time.timestamp_nanos_opt().unwrap_or_default();
+    format!("{wallet}/{nanos:020}").into_bytes()
+}
+
+impl Minter {
+    /// Save action to audit log. Failure to save is logged and doesn't stop the action
+    pub fn audit(&self, wallet: &str, action: AuditAction, txid: Option<bitcoin::Txid>, details: impl Display) {
+        let now = chrono::Utc::now();
+        let details = match self.redaction {
+            Redaction::Addresses => redact_addresses(&details.to_string()),
+            Redaction::None | Redaction::Secrets => details.to_string(),
+        };
+        let entry = AuditEntry {
+            timestamp: now.timestamp_millis(),
+            wallet: wallet.to_owned(),
+            action,
+            txid,
+            details,
+        };
+        let saved = self.tables.audit.table().and_then(|table| self.db.set(&table, audit_key(wallet, now), &entry));
+        if let Err(err) = saved {
+            warn!("Failed to save audit entry {entry:?}: {err}");
+        }
+    }
+
+    /// Audit entries of `wallet` or of all wallets, oldest first
+    pub fn audit_entries(&self, wallet: Option<&str>) -> anyhow::Result<Vec<AuditEntry>> {
+        let prefix = wallet.map(|x| format!("{x}/").into_bytes()).unwrap_or_default();
+        let mut entries = self.db
+            .iterate(&self.tables.audit.table()?, prefix)
+            .context("Failed to query audit log")?
+            .filter_map(|(_, val)| {
+                let entry = bincode::deserialize::<AuditEntry>(&val);
+                if entry.is_err() {
+                    error!("Invalid audit entry format. Skipping");
+                }
+                entry.ok()
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|x| x.timestamp);
+        Ok(entries)
+    }
+
+    /// Append secret to secrets file if redaction is `none`. Secrets are never saved anywhere else in plain text
+    pub(crate) fn backup_secret(&self, wallet: &str, kind: &str, address: Option<&str>, secret: &str) {
+        let Some(path) = &self.secrets_backup else { return };
+        let line = SecretBackup {
+            time: chrono::Utc::now().to_rfc3339(),
+            wallet,
+            kind,
+            address,
+            secret,
+        };
+        if let Err(err) = append_json_line(path, &line) {
+            warn!("Failed to backup {kind} of #{wallet} to {}: {err:#}", path.display());
+        }
+    }
+}
+
+/// Replace words of `details` which are addresses or public keys
+fn redact_addresses(details: &str) -> String {
+    details.split(' ')
+        .map(|word| match bitcoin::Address::from_str(word).is_ok() || bitcoin::PublicKey::from_str(word).is_ok() {
+            true => "<redacted>",
+            false => word,
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn append_json_line(path: &Path, value: &impl serde::Serialize) -> anyhow::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.append(true).create(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut line = serde_json::to_vec(value).context("Failed to serialize line")?;
+    line.push(b'\n');
+    options.open(path).context("Failed to open file")?
+        .write_all(&line).context("Failed to write file")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addresses_and_public_keys_are_redacted() {
+        let address = crate::minter::testing::foreign_address();
+        let public_key = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        assert_eq!(redact_addresses(&format!("Sent 1000 to {address}")), "Sent 1000 to <redacted>");
+        assert_eq!(redact_addresses(&format!("Added Utxo address {public_key}")), "Added Utxo address <redacted>");
+        assert_eq!(redact_addresses("Frozen 0000000000000000000000000000000000000000000000000000000000000000:1"), "Frozen 0000000000000000000000000000000000000000000000000000000000000000:1");
+    }
+}
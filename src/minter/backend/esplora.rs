@@ -0,0 +1,202 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use anyhow::{bail, Context};
+use serde::de::DeserializeOwned;
+
+use crate::minter::utxo::{InscriptionId, InscriptionMeta, Status};
+
+use super::{is_already_known, send_with_retry, AddressStats, BackendFuture, ChainBackend, ChainTx, TxInput, TxOutput, Utxo, REQUEST_TIMEOUT};
+
+// bincode does not support 'flatten' but we need it to access api
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UtxoApiData {
+    pub txid: String,
+    pub vout: u32,
+    pub status: Status,
+    pub value: u64,
+    #[serde(default, flatten)] pub inscription_meta: Option<InscriptionApiMeta>,
+    #[serde(default)] pub owner: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct InscriptionApiMeta {
+    pub content_type: String,
+    pub content_length: usize,
+    pub outpoint: bitcoin::Txid,
+    pub genesis: bitcoin::Txid,
+    pub inscription_id: InscriptionId,
+    pub number: usize,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TxApiData {
+    txid: bitcoin::Txid,
+    vin: Vec<TxApiInput>,
+    vout: Vec<TxApiOutput>,
+    fee: u64,
+    status: Status,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TxApiInput {
+    txid: bitcoin::Txid,
+    vout: u32,
+    prevout: Option<TxApiOutput>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct TxApiOutput {
+    scriptpubkey_address: Option<String>,
+    value: u64,
+}
+
+impl From<TxApiOutput> for TxOutput {
+    fn from(value: TxApiOutput) -> Self {
+        Self { address: value.scriptpubkey_address, value: value.value }
+    }
+}
+
+fn is_transient(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Esplora-compatible HTTP API. Utxo's of ord indexing API also carry inscription meta
+pub struct EsploraBackend {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl EsploraBackend {
+    pub fn new(url: String) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder().user_agent("rust").timeout(REQUEST_TIMEOUT).build().context("Failed to build reqwest client")?;
+        Ok(Self { client, url })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{path}", self.url.trim_end_matches('/'))
+    }
+
+    async fn get(&self, path: &str, request: &str) -> anyhow::Result<reqwest::Response> {
+        let url = self.url(path);
+        let resp = send_with_retry(|| self.client.get(&url), true, is_transient).await.with_context(|| format!("Failed to send api {request} request"))?;
+        match resp.status() {
+            reqwest::StatusCode::OK => Ok(resp),
+            err => bail!("Api {request} error: {err}"),
+        }
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, path: &str, request: &str) -> anyhow::Result<T> {
+        self.get(path, request).await?.json::<T>().await.with_context(|| format!("Api {request} invalid json"))
+    }
+
+    async fn get_text(&self, path: &str, request: &str) -> anyhow::Result<String> {
+        self.get(path, request).await?.text().await.with_context(|| format!("Api {request} invalid response"))
+    }
+}
+
+impl ChainBackend for EsploraBackend {
+    fn utxo<'a>(&'a self, address: &'a str) -> BackendFuture<'a, Vec<Utxo>> {
+        Box::pin(async move {
+            self.get_json::<Vec<UtxoApiData>>(&format!("address/{address}/utxo"), "get utxo").await?
+                .into_iter()
+                .map(|x| Ok(Utxo {
+                    txid: bitcoin::Txid::from_str(&x.txid).context("Api get utxo invalid txid")?,
+                    vout: x.vout,
+                    status: x.status,
+                    value: x.value,
+                    inscription_meta: x.inscription_meta.map(|x| InscriptionMeta {
+                        content_type: x.content_type,
+                        content_length: x.content_length,
+                        outpoint: bitcoin::OutPoint { txid: x.outpoint, vout: 0 },
+                        genesis: bitcoin::OutPoint { txid: x.genesis, vout: 0 },
+                        inscription_id: x.inscription_id,
+                        number: x.number,
+                    }),
+                    owner: x.owner,
+                }))
+                .collect()
+        })
+    }
+
+    fn address_stats<'a>(&'a self, address: &'a str) -> BackendFuture<'a, AddressStats> {
+        Box::pin(async move { self.get_json(&format!("address/{address}"), "get address").await })
+    }
+
+    fn transaction<'a>(&'a self, txid: &'a bitcoin::Txid) -> BackendFuture<'a, bitcoin::Transaction> {
+        Box::pin(async move {
+            let hex = self.get_text(&format!("tx/{txid}/hex"), "get transaction").await?;
+            let bytes = hex::decode(hex.trim()).context("Api get transaction returned invalid hex")?;
+            bitcoin::consensus::deserialize(&bytes).context("Api get transaction returned invalid transaction")
+        })
+    }
+
+    fn tx_status<'a>(&'a self, txid: &'a bitcoin::Txid) -> BackendFuture<'a, Status> {
+        Box::pin(async move { self.get_json(&format!("tx/{txid}/status"), "get transaction status").await })
+    }
+
+    fn address_txs<'a>(&'a self, address: &'a str, last_seen: Option<bitcoin::Txid>) -> BackendFuture<'a, Vec<ChainTx>> {
+        Box::pin(async move {
+            let path = match last_seen {
+                Some(txid) => format!("address/{address}/txs/chain/{txid}"),
+                None => format!("address/{address}/txs"),
+            };
+            Ok(self.get_json::<Vec<TxApiData>>(&path, "get transactions").await?
+                .into_iter()
+                .map(|x| ChainTx {
+                    txid: x.txid,
+                    inputs: x.vin.into_iter().map(|x| TxInput {
+                        previous_output: bitcoin::OutPoint { txid: x.txid, vout: x.vout },
+                        prevout: x.prevout.map(Into::into),
+                    }).collect(),
+                    outputs: x.vout.into_iter().map(Into::into).collect(),
+                    fee: x.fee,
+                    status: x.status,
+                })
+                .collect())
+        })
+    }
+
+    fn broadcast<'a>(&'a self, tx: &'a bitcoin::Transaction) -> BackendFuture<'a, bitcoin::Txid> {
+        Box::pin(async move {
+            let url = self.url("tx");
+            let hex = bitcoin::consensus::encode::serialize_hex(tx);
+            // rejected transactions may get server errors, so only rate limiting is retried
+            let resp = send_with_retry(|| self.client.post(&url).body(hex.clone()), false, |x| x == reqwest::StatusCode::TOO_MANY_REQUESTS)
+                .await
+                .context("Failed to send api broadcast request")?;
+
+            match resp.status() {
+                reqwest::StatusCode::OK => {
+                    let txid = resp.text().await.context("Api broadcast invalid response")?;
+                    bitcoin::Txid::from_str(txid.trim()).context("Api broadcast returned invalid txid")
+                }
+                err => {
+                    let msg = resp.text().await.unwrap_or_default();
+                    if is_already_known(&msg) {
+                        debug!("Tx {} is already known: {msg}", tx.txid());
+                        return Ok(tx.txid());
+                    }
+                    bail!("Api broadcast error: {err} {msg}")
+                }
+            }
+        })
+    }
+
+    fn tip_height(&self) -> BackendFuture<'_, u64> {
+        Box::pin(async move {
+            self.get_text("blocks/tip/height", "get tip height").await?
+                .trim()
+                .parse()
+                .context("Api get tip height returned invalid height")
+        })
+    }
+
+    fn fee_estimates(&self) -> BackendFuture<'_, BTreeMap<u16, f64>> {
+        Box::pin(async move {
+            self.get_json::<BTreeMap<String, f64>>("fee-estimates", "get fee estimates").await?
+                .into_iter()
+                .map(|(target, rate)| Ok((target.parse().context("Api get fee estimates returned invalid target")?, rate)))
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::Context;
+
+use crate::minter::utxo::Status;
+
+use super::{AddressStats, BackendFuture, ChainBackend, ChainTx, TxStats, Utxo, CHAIN_TXS_PAGE};
+
+#[derive(Debug, Default)]
+pub struct MockState {
+    pub utxo: HashMap<String, Vec<Utxo>>,
+    /// Stats returned for address. Computed from its utxo's if not set
+    pub stats: HashMap<String, AddressStats>,
+    pub transactions: HashMap<bitcoin::Txid, bitcoin::Transaction>,
+    /// Status of transaction. Known transactions without status are unconfirmed
+    pub tx_status: HashMap<bitcoin::Txid, Status>,
+    /// History of address, newest first
+    pub address_txs: HashMap<String, Vec<ChainTx>>,
+    /// Transactions passed to `broadcast` in order
+    pub broadcasted: Vec<bitcoin::Transaction>,
+    pub height: u64,
+    pub fee_estimates: BTreeMap<u16, f64>,
+}
+
+/// In-memory backend for tests. Broadcast transactions spend mocked utxo's but create no new ones
+#[derive(Debug, Default)]
+pub struct MockBackend {
+    state: parking_lot::Mutex<MockState>,
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> parking_lot::MutexGuard<'_, MockState> {
+        self.state.lock()
+    }
+
+    pub fn add_utxo(&self, address: &str, utxo: Utxo) {
+        self.state().utxo.entry(address.to_owned()).or_default().push(utxo);
+    }
+}
+
+impl ChainBackend for MockBackend {
+    fn utxo<'a>(&'a self, address: &'a str) -> BackendFuture<'a, Vec<Utxo>> {
+        let utxo = self.state().utxo.get(address).cloned().unwrap_or_default();
+        Box::pin(async move { Ok(utxo) })
+    }
+
+    fn address_stats<'a>(&'a self, address: &'a str) -> BackendFuture<'a, AddressStats> {
+        let state = self.state();
+        let stats = state.stats.get(address).cloned().unwrap_or_else(|| {
+            let utxo = state.utxo.get(address).map(Vec::as_slice).unwrap_or_default();
+            let stats = |confirmed: bool| {
+                let utxo = utxo.iter().filter(|x| x.status.confirmed == confirmed);
+                TxStats {
+                    tx_count: utxo.clone().map(|x| x.txid).collect::<HashSet<_>>().len() as u64,
+                    funded_txo_sum: utxo.map(|x| x.value).sum(),
+                    spent_txo_sum: 0,
+                }
+            };
+            AddressStats { address: address.to_owned(), chain_stats: stats(true), mempool_stats: stats(false) }
+        });
+        Box::pin(async move { Ok(stats) })
+    }
+
+    fn transaction<'a>(&'a self, txid: &'a bitcoin::Txid) -> BackendFuture<'a, bitcoin::Transaction> {
+        let tx = self.state().transactions.get(txid).cloned().with_context(|| format!("Transaction {txid} not found"));
+        Box::pin(async move { tx })
+    }
+
+    fn tx_status<'a>(&'a self, txid: &'a bitcoin::Txid) -> BackendFuture<'a, Status> {
+        let state = self.state();
+        let status = match state.tx_status.get(txid) {
+            Some(status) => Ok(status.clone()),
+            None if state.transactions.contains_key(txid) => Ok(Status { confirmed: false, block_height: None, block_hash: None, block_time: None }),
+            None => Err(anyhow::anyhow!("Transaction {txid} not found")),
+        };
+        Box::pin(async move { status })
+    }
+
+    fn address_txs<'a>(&'a self, address: &'a str, last_seen: Option<bitcoin::Txid>) -> BackendFuture<'a, Vec<ChainTx>> {
+        let history = self.state().address_txs.get(address).cloned().unwrap_or_default();
+        let (mempool, confirmed): (Vec<_>, Vec<_>) = history.into_iter().partition(|x| !x.status.confirmed);
+        let page = match last_seen {
+            Some(txid) => confirmed.into_iter().skip_while(|x| x.txid != txid).skip(1).take(CHAIN_TXS_PAGE).collect(),
+            None => mempool.into_iter().chain(confirmed.into_iter().take(CHAIN_TXS_PAGE)).collect(),
+        };
+        Box::pin(async move { Ok(page) })
+    }
+
+    fn broadcast<'a>(&'a self, tx: &'a bitcoin::Transaction) -> BackendFuture<'a, bitcoin::Txid> {
+        let mut state = self.state();
+        let spent = tx.input.iter().map(|x| x.previous_output).collect::<HashSet<_>>();
+        for utxo in state.utxo.values_mut() {
+            utxo.retain(|x| !spent.contains(&bitcoin::OutPoint { txid: x.txid, vout: x.vout }));
+        }
+        state.transactions.insert(tx.txid(), tx.clone());
+        state.broadcasted.push(tx.clone());
+        let txid = tx.txid();
+        Box::pin(async move { Ok(txid) })
+    }
+
+    fn tip_height(&self) -> BackendFuture<'_, u64> {
+        let height = self.state().height;
+        Box::pin(async move { Ok(height) })
+    }
+
+    fn fee_estimates(&self) -> BackendFuture<'_, BTreeMap<u16, f64>> {
+        let estimates = self.state().fee_estimates.clone();
+        Box::pin(async move { Ok(estimates) })
+    }
+}
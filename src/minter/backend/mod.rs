@@ -0,0 +1,231 @@
+use std::{collections::BTreeMap, future::Future, pin::Pin, time::Duration};
+
+use super::utxo::{InscriptionMeta, Status};
+
+pub mod esplora;
+pub mod mock;
+pub mod offline;
+pub mod rpc;
+
+pub use self::{esplora::EsploraBackend, mock::MockBackend, offline::OfflineBackend, rpc::{RpcAuth, RpcBackend}};
+
+/// Attempts of request failing with transient error before giving up
+pub const MAX_ATTEMPTS: u32 = 5;
+/// Delay before first retry, doubled after every next attempt
+pub const RETRY_DELAY: Duration = Duration::from_millis(500);
+/// Timeout of a single request
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Confirmed transactions in a full page of address history
+pub const CHAIN_TXS_PAGE: usize = 25;
+
+pub type BackendFuture<'a, T> = Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackendKind {
+    /// Esplora-compatible HTTP API of `--api-url`
+    #[default]
+    Esplora,
+    /// JSON-RPC of bellsd node
+    Rpc,
+}
+
+/// Unspent output of address as reported by backend
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Utxo {
+    pub txid: bitcoin::Txid,
+    pub vout: u32,
+    pub status: Status,
+    pub value: u64,
+    pub inscription_meta: Option<InscriptionMeta>,
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AddressStats {
+    pub address: String,
+    pub chain_stats: TxStats,
+    pub mempool_stats: TxStats,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct TxStats {
+    pub tx_count: u64,
+    pub funded_txo_sum: u64,
+    pub spent_txo_sum: u64,
+}
+
+/// Transaction of address history with values and addresses of spent outputs
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChainTx {
+    pub txid: bitcoin::Txid,
+    pub inputs: Vec<TxInput>,
+    pub outputs: Vec<TxOutput>,
+    pub fee: u64,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TxInput {
+    pub previous_output: bitcoin::OutPoint,
+    /// Spent output, None for coinbase
+    pub prevout: Option<TxOutput>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TxOutput {
+    /// None if script has no address
+    pub address: Option<String>,
+    pub value: u64,
+}
+
+impl AddressStats {
+    /// Address received or spent anything, including unconfirmed transactions
+    pub fn is_used(&self) -> bool {
+        self.chain_stats.tx_count + self.mempool_stats.tx_count > 0
+    }
+}
+
+/// Source of chain data and relay of transactions used by the minter
+pub trait ChainBackend: Send + Sync {
+    /// Unspent outputs of `address`, including unconfirmed ones if backend knows them
+    fn utxo<'a>(&'a self, address: &'a str) -> BackendFuture<'a, Vec<Utxo>>;
+    /// Confirmed and mempool transaction stats of `address`
+    fn address_stats<'a>(&'a self, address: &'a str) -> BackendFuture<'a, AddressStats>;
+    fn transaction<'a>(&'a self, txid: &'a bitcoin::Txid) -> BackendFuture<'a, bitcoin::Transaction>;
+    /// Confirmation status of transaction in chain or mempool
+    fn tx_status<'a>(&'a self, txid: &'a bitcoin::Txid) -> BackendFuture<'a, Status>;
+    /// Page of `address` history, newest first. First page (`last_seen` is None) has all mempool transactions
+    /// and up to `CHAIN_TXS_PAGE` confirmed ones, next pages have confirmed transactions older than `last_seen`
+    fn address_txs<'a>(&'a self, address: &'a str, last_seen: Option<bitcoin::Txid>) -> BackendFuture<'a, Vec<ChainTx>>;
+    /// Broadcast signed transaction
+    fn broadcast<'a>(&'a self, tx: &'a bitcoin::Transaction) -> BackendFuture<'a, bitcoin::Txid>;
+    /// Height of best block
+    fn tip_height(&self) -> BackendFuture<'_, u64>;
+    /// Fee rates in nook/vB by confirmation target in blocks
+    fn fee_estimates(&self) -> BackendFuture<'_, BTreeMap<u16, f64>>;
+}
+
+/// Node rejection reasons of broadcast transaction which is already in mempool or chain
+const ALREADY_KNOWN: &[&str] = &["txn-already-in-mempool", "txn-already-known", "already in block chain"];
+
+/// Broadcast was rejected only because transaction is already relayed or mined, e.g. by previous attempt
+/// whose response was lost
+pub fn is_already_known(reason: &str) -> bool {
+    ALREADY_KNOWN.iter().any(|x| reason.contains(x))
+}
+
+/// Send request built by `request`, retrying with exponential backoff while it fails to connect or gets
+/// response status accepted by `is_transient`. Timed out request may have been handled by server,
+/// so it is retried only if `idempotent`. Result of the last attempt is returned
+pub async fn send_with_retry(
+    request: impl Fn() -> reqwest::RequestBuilder,
+    idempotent: bool,
+    is_transient: impl Fn(reqwest::StatusCode) -> bool,
+) -> reqwest::Result<reqwest::Response> {
+    let mut delay = RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        let result = request().send().await;
+        let reason = match &result {
+            Ok(resp) if is_transient(resp.status()) => resp.status().to_string(),
+            Ok(_) => return result,
+            Err(err) if err.is_connect() || (idempotent && err.is_timeout()) => err.to_string(),
+            Err(_) => return result,
+        };
+        if attempt >= MAX_ATTEMPTS {
+            return result;
+        }
+        // server may ask to wait longer than the backoff
+        let retry_after = result.as_ref().ok()
+            .and_then(|x| x.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or_default();
+        let wait = delay.max(retry_after);
+        debug!("Request failed ({reason}), retrying in {wait:?} (attempt {attempt} of {MAX_ATTEMPTS})");
+        tokio::time::sleep(wait).await;
+        delay *= 2;
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::{Read, Write}, net::TcpListener, sync::{atomic::{AtomicUsize, Ordering}, Arc}};
+
+    use super::*;
+
+    /// Server answering requests with `responses` in order, or never answering after they run out.
+    /// Returns its url and number of accepted connections
+    fn serve(responses: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let counter = accepted.clone();
+        std::thread::spawn(move || {
+            let mut responses = responses.into_iter();
+            let mut idle = vec![];
+            for mut stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let _ = stream.read(&mut [0; 4096]);
+                match responses.next() {
+                    Some(status) => {
+                        let _ = write!(stream, "HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+                    }
+                    None => idle.push(stream),
+                }
+            }
+        });
+        (url, accepted)
+    }
+
+    fn client() -> reqwest::Client {
+        reqwest::Client::builder().timeout(Duration::from_millis(200)).build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn timed_out_request_is_not_retried_if_not_idempotent() {
+        let (url, accepted) = serve(vec![]);
+        let client = client();
+        let result = send_with_retry(|| client.post(&url), false, |x| x == reqwest::StatusCode::TOO_MANY_REQUESTS).await;
+        assert!(result.unwrap_err().is_timeout());
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn timed_out_idempotent_request_is_retried() {
+        let (url, accepted) = serve(vec![]);
+        let client = client();
+        let result = send_with_retry(|| client.get(&url), true, |_| false).await;
+        assert!(result.unwrap_err().is_timeout());
+        assert_eq!(accepted.load(Ordering::SeqCst), MAX_ATTEMPTS as usize);
+    }
+
+    #[tokio::test]
+    async fn transient_status_is_retried() {
+        let (url, accepted) = serve(vec!["429 Too Many Requests", "200 OK"]);
+        let client = client();
+        let result = send_with_retry(|| client.post(&url), false, |x| x == reqwest::StatusCode::TOO_MANY_REQUESTS).await;
+        assert_eq!(result.unwrap().status(), reqwest::StatusCode::OK);
+        assert_eq!(accepted.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_connection_is_retried() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let client = client();
+        let attempts = AtomicUsize::new(0);
+        let result = send_with_retry(|| { attempts.fetch_add(1, Ordering::SeqCst); client.post(&url) }, false, |_| false).await;
+        assert!(result.unwrap_err().is_connect());
+        assert_eq!(attempts.load(Ordering::SeqCst), MAX_ATTEMPTS as usize);
+    }
+
+    #[test]
+    fn already_known_rejections() {
+        assert!(is_already_known(r#"sendrawtransaction RPC error: {"code":-26,"message":"txn-already-in-mempool"}"#));
+        assert!(is_already_known("Transaction already in block chain"));
+        assert!(!is_already_known(r#"sendrawtransaction RPC error: {"code":-26,"message":"insufficient priority"}"#));
+    }
+}
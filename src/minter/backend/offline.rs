@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use anyhow::bail;
+
+use crate::minter::utxo::Status;
+
+use super::{AddressStats, BackendFuture, ChainBackend, ChainTx, Utxo};
+
+/// Backend of `--offline` mode. Every request fails, so keys of offline wallets never touch network
+#[derive(Debug, Default)]
+pub struct OfflineBackend;
+
+fn refuse<'a, T: Send + 'a>(request: &'static str) -> BackendFuture<'a, T> {
+    Box::pin(async move { bail!("Can't {request}: network access is disabled by --offline") })
+}
+
+impl ChainBackend for OfflineBackend {
+    fn utxo<'a>(&'a self, _: &'a str) -> BackendFuture<'a, Vec<Utxo>> {
+        refuse("get utxo")
+    }
+
+    fn address_stats<'a>(&'a self, _: &'a str) -> BackendFuture<'a, AddressStats> {
+        refuse("get address")
+    }
+
+    fn transaction<'a>(&'a self, _: &'a bitcoin::Txid) -> BackendFuture<'a, bitcoin::Transaction> {
+        refuse("get transaction")
+    }
+
+    fn tx_status<'a>(&'a self, _: &'a bitcoin::Txid) -> BackendFuture<'a, Status> {
+        refuse("get transaction status")
+    }
+
+    fn address_txs<'a>(&'a self, _: &'a str, _: Option<bitcoin::Txid>) -> BackendFuture<'a, Vec<ChainTx>> {
+        refuse("get transactions")
+    }
+
+    fn broadcast<'a>(&'a self, _: &'a bitcoin::Transaction) -> BackendFuture<'a, bitcoin::Txid> {
+        refuse("broadcast transaction")
+    }
+
+    fn tip_height(&self) -> BackendFuture<'_, u64> {
+        refuse("get tip height")
+    }
+
+    fn fee_estimates(&self) -> BackendFuture<'_, BTreeMap<u16, f64>> {
+        refuse("get fee estimates")
+    }
+}
@@ -0,0 +1,298 @@
+use std::{collections::{BTreeMap, HashSet}, path::PathBuf, sync::atomic::{AtomicU64, Ordering}};
+
+use anyhow::{bail, Context};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+use crate::minter::utxo::Status;
+
+use super::{is_already_known, send_with_retry, AddressStats, BackendFuture, ChainBackend, ChainTx, TxStats, Utxo, REQUEST_TIMEOUT};
+
+/// Confirmation targets queried with `estimatesmartfee`
+const FEE_TARGETS: [u16; 6] = [1, 2, 3, 6, 12, 24];
+
+/// Error code of wallet RPC when node has no wallet loaded
+const RPC_WALLET_NOT_FOUND: i64 = -18;
+/// Error code of unknown method, which wallet RPCs are when node runs with wallet disabled
+const RPC_METHOD_NOT_FOUND: i64 = -32601;
+/// Error code of `sendrawtransaction` when transaction is already mined
+const RPC_VERIFY_ALREADY_IN_CHAIN: i64 = -27;
+
+#[derive(Debug, Clone)]
+pub enum RpcAuth {
+    None,
+    UserPass(String, String),
+    /// `.cookie` file written by node. Read on every call, as node rewrites it on restart
+    Cookie(PathBuf),
+}
+
+#[derive(serde::Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl std::fmt::Display for RpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+#[derive(serde::Deserialize)]
+struct ScanResult {
+    success: bool,
+    #[serde(default)] unspents: Vec<ScannedUtxo>,
+}
+
+#[derive(serde::Deserialize)]
+struct ScannedUtxo {
+    txid: bitcoin::Txid,
+    vout: u32,
+    amount: f64,
+    height: usize,
+}
+
+#[derive(serde::Deserialize)]
+struct WalletUtxo {
+    txid: bitcoin::Txid,
+    vout: u32,
+    amount: f64,
+}
+
+#[derive(serde::Deserialize)]
+struct RawTxInfo {
+    blockhash: Option<bitcoin::BlockHash>,
+    blocktime: Option<u32>,
+    #[serde(default)] confirmations: u64,
+}
+
+#[derive(serde::Deserialize)]
+struct SmartFee {
+    feerate: Option<f64>,
+}
+
+/// JSON-RPC of bellsd node. Confirmed utxo's are found with `scantxoutset`, so node needs no wallet
+/// or address index. `scantxoutset` also reports outputs already spent in mempool, so every found output
+/// is checked with `gettxout`. Unconfirmed utxo's are only known for addresses imported to node wallet.
+/// Without address index spent history is unknown, so address stats only count unspent outputs.
+/// Transactions and their status are fetched with `getrawtransaction`, which only finds confirmed
+/// transactions if node runs with `-txindex`
+pub struct RpcBackend {
+    client: reqwest::Client,
+    url: String,
+    auth: RpcAuth,
+    id: AtomicU64,
+    /// Node runs only one `scantxoutset` at a time and fails others
+    scan_lock: tokio::sync::Mutex<()>,
+}
+
+impl RpcBackend {
+    pub fn new(url: String, auth: RpcAuth) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder().user_agent("rust").timeout(REQUEST_TIMEOUT).build().context("Failed to build reqwest client")?;
+        Ok(Self { client, url, auth, id: AtomicU64::new(0), scan_lock: Default::default() })
+    }
+
+    async fn call<T: DeserializeOwned>(&self, method: &str, params: serde_json::Value) -> anyhow::Result<T> {
+        self.call_optional(method, params).await?.with_context(|| format!("RPC {method} returned no result"))
+    }
+
+    /// Call `method` which may return null
+    async fn call_optional<T: DeserializeOwned>(&self, method: &str, params: serde_json::Value) -> anyhow::Result<Option<T>> {
+        self.send(method, params, true).await
+    }
+
+    /// Call `method` which changes node state, so it isn't sent again after timeout
+    async fn call_once<T: DeserializeOwned>(&self, method: &str, params: serde_json::Value) -> anyhow::Result<T> {
+        self.send(method, params, false).await?.with_context(|| format!("RPC {method} returned no result"))
+    }
+
+    async fn send<T: DeserializeOwned>(&self, method: &str, params: serde_json::Value, idempotent: bool) -> anyhow::Result<Option<T>> {
+        let body = json!({
+            "jsonrpc": "1.0",
+            "id": self.id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+        let auth = match &self.auth {
+            RpcAuth::None => None,
+            RpcAuth::UserPass(user, password) => Some((user.clone(), password.clone())),
+            RpcAuth::Cookie(path) => {
+                let cookie = std::fs::read_to_string(path).with_context(|| format!("Failed to read RPC cookie {}", path.display()))?;
+                let (user, password) = cookie.trim().split_once(':').context("Invalid RPC cookie")?;
+                Some((user.to_owned(), password.to_owned()))
+            }
+        };
+        let request = || {
+            let request = self.client.post(&self.url).json(&body);
+            match &auth {
+                Some((user, password)) => request.basic_auth(user, Some(password)),
+                None => request,
+            }
+        };
+
+        // node answers failed calls with 500, so only full work queue is retried. Calls changing node state
+        // are retried only if they didn't reach it
+        let resp = send_with_retry(request, idempotent, |x| idempotent && x == reqwest::StatusCode::SERVICE_UNAVAILABLE)
+            .await
+            .with_context(|| format!("Failed to send RPC {method} request"))?;
+        if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+            bail!("RPC {method} error: authentication failed");
+        }
+        let resp = resp.json::<RpcResponse<T>>().await.with_context(|| format!("RPC {method} invalid json"))?;
+        if let Some(err) = resp.error {
+            return Err(anyhow::Error::new(err).context(format!("RPC {method} failed")));
+        }
+        Ok(resp.result)
+    }
+
+    /// Unconfirmed utxo's of address imported to node wallet. Empty if node has no wallet loaded
+    async fn mempool_utxo(&self, address: &str) -> anyhow::Result<Vec<WalletUtxo>> {
+        match self.call::<Vec<WalletUtxo>>("listunspent", json!([0, 0, [address]])).await {
+            Ok(utxo) => Ok(utxo),
+            Err(err) if err.downcast_ref::<RpcError>().is_some_and(|x| [RPC_WALLET_NOT_FOUND, RPC_METHOD_NOT_FOUND].contains(&x.code)) => {
+                debug!("Unconfirmed utxo of {address} are unknown: {err}");
+                Ok(vec![])
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Confirmed utxo's of address, except ones spent by mempool transactions
+    async fn scan_utxo(&self, address: &str) -> anyhow::Result<Vec<ScannedUtxo>> {
+        let scan = {
+            let _scan = self.scan_lock.lock().await;
+            self.call::<ScanResult>("scantxoutset", json!(["start", [format!("addr({address})")]])).await?
+        };
+        if !scan.success {
+            bail!("RPC scantxoutset of {address} was aborted");
+        }
+
+        let mut unspent = vec![];
+        for x in scan.unspents {
+            // null if output is spent, including spends in mempool
+            if self.call_optional::<serde_json::Value>("gettxout", json!([x.txid, x.vout, true])).await?.is_some() {
+                unspent.push(x);
+            } else {
+                debug!("Output {}:{} of {address} is spent in mempool", x.txid, x.vout);
+            }
+        }
+        Ok(unspent)
+    }
+}
+
+fn to_sat(amount: f64) -> anyhow::Result<u64> {
+    Ok(bitcoin::Amount::from_btc(amount).context("RPC returned invalid amount")?.to_sat())
+}
+
+impl ChainBackend for RpcBackend {
+    fn utxo<'a>(&'a self, address: &'a str) -> BackendFuture<'a, Vec<Utxo>> {
+        Box::pin(async move {
+            let mut utxo = vec![];
+            for x in self.scan_utxo(address).await? {
+                utxo.push(Utxo {
+                    txid: x.txid,
+                    vout: x.vout,
+                    status: Status { confirmed: true, block_height: Some(x.height), block_hash: None, block_time: None },
+                    value: to_sat(x.amount)?,
+                    inscription_meta: None,
+                    owner: None,
+                });
+            }
+            for x in self.mempool_utxo(address).await? {
+                utxo.push(Utxo {
+                    txid: x.txid,
+                    vout: x.vout,
+                    status: Status { confirmed: false, block_height: None, block_hash: None, block_time: None },
+                    value: to_sat(x.amount)?,
+                    inscription_meta: None,
+                    owner: None,
+                });
+            }
+            Ok(utxo)
+        })
+    }
+
+    fn address_stats<'a>(&'a self, address: &'a str) -> BackendFuture<'a, AddressStats> {
+        Box::pin(async move {
+            let stats = |txids: HashSet<bitcoin::Txid>, sum: u64| TxStats { tx_count: txids.len() as u64, funded_txo_sum: sum, spent_txo_sum: 0 };
+
+            let scanned = self.scan_utxo(address).await?;
+            let mut sum = 0;
+            for x in &scanned { sum += to_sat(x.amount)?; }
+            let chain_stats = stats(scanned.iter().map(|x| x.txid).collect(), sum);
+
+            let mempool = self.mempool_utxo(address).await?;
+            let mut sum = 0;
+            for x in &mempool { sum += to_sat(x.amount)?; }
+            let mempool_stats = stats(mempool.iter().map(|x| x.txid).collect(), sum);
+
+            Ok(AddressStats { address: address.to_owned(), chain_stats, mempool_stats })
+        })
+    }
+
+    fn transaction<'a>(&'a self, txid: &'a bitcoin::Txid) -> BackendFuture<'a, bitcoin::Transaction> {
+        Box::pin(async move {
+            let hex = self.call::<String>("getrawtransaction", json!([txid, false])).await?;
+            let bytes = hex::decode(hex).context("RPC getrawtransaction returned invalid hex")?;
+            bitcoin::consensus::deserialize(&bytes).context("RPC getrawtransaction returned invalid transaction")
+        })
+    }
+
+    fn tx_status<'a>(&'a self, txid: &'a bitcoin::Txid) -> BackendFuture<'a, Status> {
+        Box::pin(async move {
+            let info = self.call::<RawTxInfo>("getrawtransaction", json!([txid, true])).await?;
+            if info.confirmations == 0 {
+                return Ok(Status { confirmed: false, block_height: None, block_hash: None, block_time: None });
+            }
+            let tip = self.call::<u64>("getblockcount", json!([])).await?;
+            Ok(Status {
+                confirmed: true,
+                block_height: Some((tip + 1).saturating_sub(info.confirmations) as usize),
+                block_hash: info.blockhash,
+                block_time: info.blocktime,
+            })
+        })
+    }
+
+    fn address_txs<'a>(&'a self, _address: &'a str, _last_seen: Option<bitcoin::Txid>) -> BackendFuture<'a, Vec<ChainTx>> {
+        Box::pin(async move { bail!("Address history is not available with rpc backend, as node has no address index. Use esplora backend") })
+    }
+
+    fn broadcast<'a>(&'a self, tx: &'a bitcoin::Transaction) -> BackendFuture<'a, bitcoin::Txid> {
+        Box::pin(async move {
+            match self.call_once("sendrawtransaction", json!([bitcoin::consensus::encode::serialize_hex(tx)])).await {
+                Ok(txid) => Ok(txid),
+                Err(err) if err.downcast_ref::<RpcError>().is_some_and(|x| x.code == RPC_VERIFY_ALREADY_IN_CHAIN || is_already_known(&x.message)) => {
+                    debug!("Tx {} is already known: {err:#}", tx.txid());
+                    Ok(tx.txid())
+                }
+                Err(err) => Err(err),
+            }
+        })
+    }
+
+    fn tip_height(&self) -> BackendFuture<'_, u64> {
+        Box::pin(self.call("getblockcount", json!([])))
+    }
+
+    fn fee_estimates(&self) -> BackendFuture<'_, BTreeMap<u16, f64>> {
+        Box::pin(async move {
+            let mut estimates = BTreeMap::new();
+            for target in FEE_TARGETS {
+                // feerate is in BEL/kvB
+                if let Some(rate) = self.call::<SmartFee>("estimatesmartfee", json!([target])).await?.feerate {
+                    estimates.insert(target, rate * 100_000.0);
+                }
+            }
+            Ok(estimates)
+        })
+    }
+}
@@ -0,0 +1,392 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use anyhow::{bail, Context};
+
+use crate::{wallet::AddressType, FeeRate};
+
+use super::{audit::AuditAction, tx::{estimate_p2pkh_vsize, DUST_LIMIT}, utxo::{Status, UtxoData}, Minter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BumpMethod {
+    /// Original transaction is replaced with one paying more fee from its change
+    Rbf,
+    /// Child transaction spending change of original pays fee for both
+    Cpfp,
+}
+
+/// Fee bump made by `Minter::bump_fee`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BumpResult {
+    pub method: BumpMethod,
+    pub original: bitcoin::Txid,
+    /// Replacement or child transaction
+    pub txid: bitcoin::Txid,
+    /// Fee of replacement or child in sats
+    pub fee: u64,
+    /// Fee rate of replacement, or of original and child together, in nook/vB
+    pub fee_rate: f64,
+}
+
+/// Output spent by input of original transaction. `owner` is (address, type) if it belongs to wallet
+struct Prevout {
+    outpoint: bitcoin::OutPoint,
+    value: u64,
+    owner: Option<(String, AddressType)>,
+}
+
+impl Prevout {
+    fn utxo(&self) -> Option<(String, UtxoData)> {
+        let (address, ty) = self.owner.clone()?;
+        Some((address, UtxoData {
+            txid: self.outpoint.txid,
+            vout: self.outpoint.vout,
+            status: Status { confirmed: false, block_height: None, block_hash: None, block_time: None },
+            value: self.value,
+            ty,
+            inscription_meta: None,
+            owner: None,
+        }))
+    }
+}
+
+/// Offset after the last sat spent from ord address of wallet, counting sats of all inputs in order.
+/// Sats move to outputs first in first out, so outputs starting at or after it hold no inscribed sats
+fn inscribed_end(prevouts: &[Prevout]) -> u64 {
+    let mut offset = 0;
+    let mut end = 0;
+    for prevout in prevouts {
+        offset += prevout.value;
+        if prevout.owner.as_ref().is_some_and(|(_, ty)| *ty == AddressType::Ord) {
+            end = offset;
+        }
+    }
+    end
+}
+
+impl Minter {
+    /// Make unconfirmed `txid` of wallet pay `fee_rate`. Transaction signaling RBF with all inputs of wallet and only one
+    /// output to utxo address of wallet is replaced with one paying more from that change output. Otherwise the last output
+    /// to utxo address of wallet is spent by child paying fee of both. Sats of ord addresses are never moved to fee
+    pub async fn bump_fee(&self, wallet: &str, txid: &bitcoin::Txid, fee_rate: FeeRate) -> anyhow::Result<BumpResult> {
+        self.check_can_sign(wallet)?;
+        if self.backend.tx_status(txid).await.context("Failed to get transaction status")?.confirmed {
+            bail!("Transaction {txid} is already confirmed");
+        }
+        let tx = self.backend.transaction(txid).await.with_context(|| format!("Failed to get transaction {txid}"))?;
+        let prevouts = self.tx_prevouts(wallet, &tx).await?;
+
+        let input = prevouts.iter().map(|x| x.value).sum::<u64>();
+        let output = tx.output.iter().map(|x| x.value).sum::<u64>();
+        let fee = input.checked_sub(output).context("Transaction outputs are more than inputs")?;
+
+        let replaceable = tx.is_explicitly_rbf() && prevouts.iter().all(|x| x.owner.is_some());
+        let outputs = self.wallet_utxo_outputs(wallet, &tx)?;
+        let result = match outputs.as_slice() {
+            [] => bail!("Transaction {txid} has no output to utxo address of wallet to pay bumped fee"),
+            [(change, _)] if replaceable => self.replace_tx(wallet, tx, &prevouts, *change, fee, fee_rate).await?,
+            [.., change] => {
+                if replaceable {
+                    // payment to own utxo address can't be told from change, so none of them is reduced
+                    info!("Transaction {txid} pays to {} utxo addresses of wallet, so its change is unknown. Bumping fee with child", outputs.len());
+                } else {
+                    info!("Transaction {txid} can't be replaced. Bumping fee with child");
+                }
+                self.spend_change(wallet, tx, &prevouts, change.clone(), fee, fee_rate).await?
+            }
+        };
+
+        let details = format!("Bumped fee of {txid} with {:?} paying {} to {:.2} nook/vB", result.method, result.fee, result.fee_rate);
+        self.audit(wallet, AuditAction::BumpFee, Some(result.txid), details);
+        Ok(result)
+    }
+
+    /// Spent outputs of `tx` inputs with their wallet owners
+    async fn tx_prevouts(&self, wallet: &str, tx: &bitcoin::Transaction) -> anyhow::Result<Vec<Prevout>> {
+        let network = self.network.network();
+        let mut prev_txs = HashMap::new();
+        let mut prevouts = vec![];
+        for input in &tx.input {
+            let outpoint = input.previous_output;
+            let prev_tx = match prev_txs.entry(outpoint.txid) {
+                Entry::Occupied(x) => x.into_mut(),
+                Entry::Vacant(x) => x.insert(self.backend.transaction(&outpoint.txid).await
+                    .with_context(|| format!("Failed to get transaction {}", outpoint.txid))?),
+            };
+            let txout = prev_tx.output.get(outpoint.vout as usize).with_context(|| format!("Output {outpoint} not found"))?;
+
+            let owner = match bitcoin::Address::from_script(&txout.script_pubkey, network) {
+                Ok(address) => self.get_address(wallet, &address.to_string())?.map(|x| (address.to_string(), x.ty)),
+                Err(_) => None,
+            };
+            prevouts.push(Prevout { outpoint, value: txout.value, owner });
+        }
+        Ok(prevouts)
+    }
+
+    /// Outputs of `tx` to utxo addresses of wallet as (index, address)
+    fn wallet_utxo_outputs(&self, wallet: &str, tx: &bitcoin::Transaction) -> anyhow::Result<Vec<(usize, String)>> {
+        let mut outputs = vec![];
+        for (i, output) in tx.output.iter().enumerate() {
+            let Ok(address) = bitcoin::Address::from_script(&output.script_pubkey, self.network.network()) else { continue };
+            let address = address.to_string();
+            if self.get_address(wallet, &address)?.is_some_and(|x| x.ty == AddressType::Utxo) {
+                outputs.push((i, address));
+            }
+        }
+        Ok(outputs)
+    }
+
+    /// Replace `tx` with one paying more fee from its `change` output
+    async fn replace_tx(&self, wallet: &str, mut tx: bitcoin::Transaction, prevouts: &[Prevout], change: usize, fee: u64, fee_rate: FeeRate) -> anyhow::Result<BumpResult> {
+        let original = tx.txid();
+        let vsize = tx.vsize();
+
+        let change_start = tx.output[..change].iter().map(|x| x.value).sum::<u64>();
+        if inscribed_end(prevouts) > change_start {
+            bail!("Change output of {original} holds or is followed by inscribed sats, so it can't be reduced");
+        }
+
+        // replacement must pay for its own relay on top of fee of original
+        let new_fee = fee_rate.fee(vsize).to_sat();
+        if new_fee < fee + vsize as u64 {
+            bail!("Fee rate is too low to replace {original}. Replacement must pay at least {} sats", fee + vsize as u64);
+        }
+        let increase = new_fee - fee;
+        let change_value = tx.output[change].value;
+        if change_value < increase {
+            bail!("Change output of {original} has {change_value} sats, which is less than fee increase {increase}");
+        }
+        if change_value - increase < DUST_LIMIT {
+            debug!("Change of replacement is dust. Removing it");
+            tx.output.remove(change);
+        } else {
+            tx.output[change].value = change_value - increase;
+        }
+
+        for input in &mut tx.input {
+            input.script_sig = bitcoin::Script::new();
+        }
+        let spent = prevouts.iter().filter_map(Prevout::utxo).collect::<Vec<_>>();
+        self.sign_p2pkh_inputs(wallet, &mut tx, &spent).context("Failed to sign replacement")?;
+        let txid = self.broadcast_tx(&tx).await.context("Failed to broadcast replacement")?;
+
+        // outputs of replaced transaction will never exist
+        self.clear_saved_utxo(wallet, |_,x| x.txid == original)?;
+        self.remove_spent_by(wallet, &tx)?;
+
+        let input = prevouts.iter().map(|x| x.value).sum::<u64>();
+        let fee = input - tx.output.iter().map(|x| x.value).sum::<u64>();
+        info!("Replaced {original} with {txid} paying {fee}");
+        Ok(BumpResult {
+            method: BumpMethod::Rbf,
+            original,
+            txid,
+            fee,
+            fee_rate: fee as f64 / tx.vsize() as f64,
+        })
+    }
+
+    /// Spend `change` output of `parent`, which is (index, address), with child paying fee of both
+    async fn spend_change(&self, wallet: &str, parent: bitcoin::Transaction, prevouts: &[Prevout], (change, address): (usize, String), fee: u64, fee_rate: FeeRate) -> anyhow::Result<BumpResult> {
+        let original = parent.txid();
+        let vsize = parent.vsize();
+
+        let change_start = parent.output[..change].iter().map(|x| x.value).sum::<u64>();
+        if inscribed_end(prevouts) > change_start {
+            bail!("Change output of {original} holds inscribed sats, so it can't pay fee of child");
+        }
+
+        let child_vsize = estimate_p2pkh_vsize(1, 1);
+        let package_fee = fee_rate.fee(vsize + child_vsize).to_sat();
+        if package_fee <= fee {
+            bail!("Transaction {original} already pays {:.2} nook/vB", fee as f64 / vsize as f64);
+        }
+        // child must pay for its own relay too
+        let child_fee = (package_fee - fee).max(child_vsize as u64);
+        let value = parent.output[change].value;
+        if value < child_fee + DUST_LIMIT {
+            bail!("Change output of {original} has {value} sats, which is not enough for child fee {child_fee}");
+        }
+
+        let utxo = UtxoData {
+            txid: original,
+            vout: change as u32,
+            status: Status { confirmed: false, block_height: None, block_hash: None, block_time: None },
+            value,
+            ty: AddressType::Utxo,
+            inscription_meta: None,
+            owner: None,
+        };
+        let mut child = bitcoin::Transaction {
+            version: 1,
+            lock_time: bitcoin::PackedLockTime::ZERO,
+            input: vec![bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint { txid: original, vout: change as u32 },
+                script_sig: bitcoin::Script::new(),
+                sequence: bitcoin::Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: bitcoin::Witness::new(),
+            }],
+            output: vec![bitcoin::TxOut {
+                value: value - child_fee,
+                script_pubkey: parent.output[change].script_pubkey.clone(),
+            }],
+        };
+
+        self.sign_p2pkh_inputs(wallet, &mut child, &[(address, utxo)]).context("Failed to sign child")?;
+        let txid = self.broadcast_tx(&child).await.context("Failed to broadcast child")?;
+        self.remove_spent_by(wallet, &child)?;
+
+        info!("Child {txid} of {original} pays {child_fee}");
+        Ok(BumpResult {
+            method: BumpMethod::Cpfp,
+            original,
+            txid,
+            fee: child_fee,
+            fee_rate: (fee + child_fee) as f64 / (vsize + child.vsize()) as f64,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::minter::testing::{foreign_address, foreign_script, TestMinter, WALLET};
+
+    use super::*;
+
+    fn prevout(value: u64, ty: Option<AddressType>) -> Prevout {
+        Prevout {
+            outpoint: bitcoin::OutPoint::null(),
+            value,
+            owner: ty.map(|ty| ("address".to_owned(), ty)),
+        }
+    }
+
+    fn rate(rate: f64) -> FeeRate {
+        FeeRate::try_from(rate).unwrap()
+    }
+
+    fn input(previous_output: bitcoin::OutPoint) -> bitcoin::TxIn {
+        bitcoin::TxIn { previous_output, sequence: bitcoin::Sequence::ENABLE_RBF_NO_LOCKTIME, ..Default::default() }
+    }
+
+    /// Put unconfirmed `tx` to mock backend as if it was broadcast before
+    fn mock_tx(test: &TestMinter, tx: &bitcoin::Transaction) -> bitcoin::Txid {
+        test.backend.state().transactions.insert(tx.txid(), tx.clone());
+        tx.txid()
+    }
+
+    #[test]
+    fn inscribed_end_without_ord_inputs_is_zero() {
+        assert_eq!(inscribed_end(&[prevout(5000, Some(AddressType::Utxo)), prevout(3000, None)]), 0);
+    }
+
+    #[test]
+    fn inscribed_end_follows_the_last_ord_input() {
+        let (ord, utxo) = (Some(AddressType::Ord), Some(AddressType::Utxo));
+        assert_eq!(inscribed_end(&[prevout(1000, ord), prevout(5000, utxo)]), 1000);
+        assert_eq!(inscribed_end(&[prevout(5000, utxo), prevout(1000, ord)]), 6000);
+        assert_eq!(inscribed_end(&[prevout(5000, utxo), prevout(1000, ord), prevout(3000, utxo)]), 6000);
+        assert_eq!(inscribed_end(&[prevout(1000, ord), prevout(5000, utxo), prevout(2000, ord)]), 8000);
+        // sats of foreign inputs come first too
+        assert_eq!(inscribed_end(&[prevout(2000, None), prevout(1000, ord)]), 3000);
+    }
+
+    #[tokio::test]
+    async fn replacement_pays_more_from_change() {
+        let test = TestMinter::new();
+        test.fund(AddressType::Utxo, 1_000_000, true);
+        let txid = test.minter.send_utxo(WALLET, foreign_address(), bitcoin::Amount::from_sat(300_000), rate(1.0)).await.unwrap();
+        let original = test.backend.state().transactions[&txid].clone();
+        let original_fee = 1_000_000 - original.output.iter().map(|x| x.value).sum::<u64>();
+
+        let result = test.minter.bump_fee(WALLET, &txid, rate(5.0)).await.unwrap();
+        let replacement = test.backend.state().broadcasted.last().unwrap().clone();
+        assert_eq!(result.method, BumpMethod::Rbf);
+        assert_eq!(result.txid, replacement.txid());
+        assert_eq!(replacement.input.iter().map(|x| x.previous_output).collect::<Vec<_>>(), original.input.iter().map(|x| x.previous_output).collect::<Vec<_>>());
+        assert_eq!(replacement.output[0], original.output[0]);
+        assert_eq!(result.fee, 5 * original.vsize() as u64);
+        assert_eq!(replacement.output[1].value, original.output[1].value - (result.fee - original_fee));
+    }
+
+    #[tokio::test]
+    async fn replacement_must_pay_for_its_relay() {
+        let test = TestMinter::new();
+        test.fund(AddressType::Utxo, 1_000_000, true);
+        let txid = test.minter.send_utxo(WALLET, foreign_address(), bitcoin::Amount::from_sat(300_000), rate(1.0)).await.unwrap();
+
+        let err = test.minter.bump_fee(WALLET, &txid, rate(1.5)).await.unwrap_err();
+        assert!(err.to_string().contains("too low"), "{err:#}");
+    }
+
+    #[tokio::test]
+    async fn payment_to_own_utxo_address_is_not_reduced() {
+        let test = TestMinter::new();
+        test.fund(AddressType::Utxo, 1_000_000, true);
+        let own = test.minter.receive_address(WALLET, AddressType::Utxo).unwrap();
+        let txid = test.minter.send_utxo(WALLET, own, bitcoin::Amount::from_sat(300_000), rate(1.0)).await.unwrap();
+
+        let result = test.minter.bump_fee(WALLET, &txid, rate(5.0)).await.unwrap();
+        let child = test.backend.state().broadcasted.last().unwrap().clone();
+        assert_eq!(result.method, BumpMethod::Cpfp);
+        assert_eq!(child.input[0].previous_output, bitcoin::OutPoint { txid, vout: 1 });
+    }
+
+    #[tokio::test]
+    async fn child_pays_for_parent_with_foreign_inputs() {
+        let test = TestMinter::new();
+        let address = test.minter.receive_address(WALLET, AddressType::Utxo).unwrap();
+        let funding = bitcoin::Transaction {
+            version: 1,
+            lock_time: bitcoin::PackedLockTime::ZERO,
+            input: vec![input(bitcoin::OutPoint::null())],
+            output: vec![bitcoin::TxOut { value: 1_000_000, script_pubkey: foreign_script() }],
+        };
+        let parent = bitcoin::Transaction {
+            version: 1,
+            lock_time: bitcoin::PackedLockTime::ZERO,
+            input: vec![input(bitcoin::OutPoint { txid: mock_tx(&test, &funding), vout: 0 })],
+            output: vec![bitcoin::TxOut { value: 999_900, script_pubkey: address.script_pubkey() }],
+        };
+        let txid = mock_tx(&test, &parent);
+
+        let result = test.minter.bump_fee(WALLET, &txid, rate(10.0)).await.unwrap();
+        let child = test.backend.state().broadcasted.last().unwrap().clone();
+        let child_fee = 10 * (parent.vsize() + estimate_p2pkh_vsize(1, 1)) as u64 - 100;
+        assert_eq!(result.method, BumpMethod::Cpfp);
+        assert_eq!(result.fee, child_fee);
+        assert_eq!(child.input[0].previous_output, bitcoin::OutPoint { txid, vout: 0 });
+        assert_eq!(child.output[0], bitcoin::TxOut { value: 999_900 - child_fee, script_pubkey: address.script_pubkey() });
+    }
+
+    #[tokio::test]
+    async fn inscribed_sats_never_pay_fee() {
+        let test = TestMinter::new();
+        let (address, cardinal) = test.fund(AddressType::Utxo, 50_000, true);
+        let (_, inscribed) = test.fund(AddressType::Ord, 10_000, true);
+        // inscribed sats of the second input land in change output
+        let tx = bitcoin::Transaction {
+            version: 1,
+            lock_time: bitcoin::PackedLockTime::ZERO,
+            input: vec![input(cardinal), input(inscribed)],
+            output: vec![
+                bitcoin::TxOut { value: 49_000, script_pubkey: address.script_pubkey() },
+                bitcoin::TxOut { value: 10_000, script_pubkey: foreign_script() },
+            ],
+        };
+        let txid = mock_tx(&test, &tx);
+
+        let err = test.minter.bump_fee(WALLET, &txid, rate(50.0)).await.unwrap_err();
+        assert!(err.to_string().contains("inscribed"), "{err:#}");
+        assert!(test.backend.state().broadcasted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn confirmed_transaction_is_refused() {
+        let test = TestMinter::new();
+        let (_, outpoint) = test.fund(AddressType::Utxo, 1_000_000, true);
+        let err = test.minter.bump_fee(WALLET, &outpoint.txid, rate(5.0)).await.unwrap_err();
+        assert!(err.to_string().contains("already confirmed"), "{err:#}");
+    }
+}
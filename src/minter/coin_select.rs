@@ -0,0 +1,337 @@
+use std::fmt::{self, Display, Formatter};
+
+use bitcoin::{secp256k1::rand::{seq::SliceRandom, thread_rng, Rng}, Amount};
+
+use crate::FeeRate;
+
+use super::{tx::{DUST_LIMIT, P2PKH_INPUT_VSIZE, P2PKH_OUTPUT_VSIZE}, utxo::UtxoData};
+
+const BNB_TOTAL_TRIES: usize = 100_000;
+const KNAPSACK_ITERATIONS: usize = 1000;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CoinSelectionParams {
+    /// Value of transaction outputs to pay for
+    pub target: u64,
+    /// Value of inputs which are already in transaction
+    pub existing: u64,
+    /// Size of transaction without selected inputs and change output
+    pub base_vsize: usize,
+    pub fee_rate: FeeRate,
+}
+
+#[derive(Debug, Clone)]
+pub struct Selection {
+    pub utxo: Vec<(String, UtxoData)>,
+    /// Fee paid by transaction, including dust change added to it
+    pub fee: u64,
+    /// Value of change output or 0 if transaction has no change
+    pub change: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoinSelectionError {
+    InsufficientFunds { needed: Amount, available: Amount },
+}
+
+impl CoinSelectionError {
+    pub fn shortfall(&self) -> Amount {
+        match self {
+            Self::InsufficientFunds { needed, available } => *needed - *available,
+        }
+    }
+}
+
+impl Display for CoinSelectionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InsufficientFunds { needed, available } =>
+                write!(f, "insufficient funds: need {needed} including fee, available {available}, short by {}", self.shortfall()),
+        }
+    }
+}
+
+impl std::error::Error for CoinSelectionError {}
+
+/// Candidate with value left after paying for its own input
+struct Candidate {
+    index: usize,
+    effective: u64,
+}
+
+/// Select p2pkh utxo's to pay for `params.target` and fee of the transaction.
+/// Tries to find changeless match with branch-and-bound and falls back to knapsack with change
+pub fn select_coins(candidates: Vec<(String, UtxoData)>, params: &CoinSelectionParams) -> Result<Selection, CoinSelectionError> {
+    let input_fee = params.fee_rate.fee(P2PKH_INPUT_VSIZE).to_sat();
+    let change_fee = params.fee_rate.fee(P2PKH_OUTPUT_VSIZE).to_sat();
+    let base_fee = params.fee_rate.fee(params.base_vsize).to_sat();
+
+    let Some(need) = (params.target + base_fee).checked_sub(params.existing).filter(|x| *x > 0) else {
+        // inputs already in transaction pay for everything, so only surplus which is worth an output is left as change
+        let surplus = params.existing - params.target - base_fee;
+        let (fee, change) = if surplus >= change_fee + DUST_LIMIT {
+            (base_fee + change_fee, surplus - change_fee)
+        } else {
+            (base_fee + surplus, 0)
+        };
+        return Ok(Selection { utxo: vec![], fee, change });
+    };
+
+    let mut pool = candidates.iter()
+        .enumerate()
+        .filter_map(|(index, (_, x))| x.value.checked_sub(input_fee).filter(|x| *x > 0).map(|effective| Candidate { index, effective }))
+        .collect::<Vec<_>>();
+    pool.sort_by_key(|x| std::cmp::Reverse(x.effective));
+
+    let available = pool.iter().map(|x| x.effective).sum::<u64>();
+    if available < need {
+        return Err(CoinSelectionError::InsufficientFunds {
+            needed: Amount::from_sat(need),
+            available: Amount::from_sat(available),
+        });
+    }
+
+    // spending the change later costs an input, so changeless result may overpay by that much
+    let cost_of_change = change_fee + input_fee;
+    let selected = branch_and_bound(&pool, need, cost_of_change)
+        .or_else(|| knapsack(&pool, need + change_fee + DUST_LIMIT))
+        .unwrap_or_else(|| (0..pool.len()).collect());
+
+    let selected_effective = selected.iter().map(|&i| pool[i].effective).sum::<u64>();
+    let excess = selected_effective - need;
+    let (fee, change) = if excess >= change_fee + DUST_LIMIT {
+        (base_fee + input_fee * selected.len() as u64 + change_fee, excess - change_fee)
+    } else {
+        (base_fee + input_fee * selected.len() as u64 + excess, 0)
+    };
+
+    let mut candidates = candidates.into_iter().map(Some).collect::<Vec<_>>();
+    let utxo = selected.iter().map(|&i| candidates[pool[i].index].take().unwrap()).collect();
+
+    Ok(Selection { utxo, fee, change })
+}
+
+/// Depth-first search for a set with value in [need, need + cost_of_change]. `pool` is sorted by value descending
+fn branch_and_bound(pool: &[Candidate], need: u64, cost_of_change: u64) -> Option<Vec<usize>> {
+    let mut available = pool.iter().map(|x| x.effective).sum::<u64>();
+    let mut value = 0;
+    let mut selection: Vec<usize> = vec![];
+    let mut best: Option<(u64, Vec<usize>)> = None;
+
+    let mut index = 0;
+    for _ in 0..BNB_TOTAL_TRIES {
+        let backtrack = if value + available < need || value > need + cost_of_change {
+            true
+        } else if value >= need {
+            let waste = value - need;
+            if best.as_ref().is_none_or(|(best_waste, _)| waste <= *best_waste) {
+                best = Some((waste, selection.clone()));
+            }
+            if waste == 0 { break; }
+            true
+        } else { false };
+
+        if backtrack {
+            let Some(&last) = selection.last() else { break };
+            // add skipped candidates back and try to exclude the last included one
+            index -= 1;
+            while index > last {
+                available += pool[index].effective;
+                index -= 1;
+            }
+            value -= pool[last].effective;
+            selection.pop();
+        } else {
+            let candidate = &pool[index];
+            available -= candidate.effective;
+            // skip candidate equal to the previous excluded one, that branch was already explored
+            let previous_excluded = index > 0 && selection.last().is_some_and(|&last| last != index - 1);
+            if !previous_excluded || pool[index - 1].effective != candidate.effective {
+                selection.push(index);
+                value += candidate.effective;
+            }
+        }
+        index += 1;
+    }
+
+    best.map(|(_, x)| x)
+}
+
+/// Find smallest set covering `need`. Prefers single smallest larger candidate over worse subsets
+fn knapsack(pool: &[Candidate], need: u64) -> Option<Vec<usize>> {
+    let mut lowest_larger: Option<usize> = None;
+    let mut smaller = vec![];
+    for (i, candidate) in pool.iter().enumerate() {
+        if candidate.effective == need { return Some(vec![i]); }
+        if candidate.effective > need {
+            // pool is sorted descending, so the last larger one is the lowest
+            lowest_larger = Some(i);
+        } else {
+            smaller.push(i);
+        }
+    }
+
+    let smaller_total = smaller.iter().map(|&i| pool[i].effective).sum::<u64>();
+    if smaller_total == need { return Some(smaller); }
+    if smaller_total < need { return lowest_larger.map(|x| vec![x]); }
+
+    let (best_value, best) = approximate_best_subset(pool, &smaller, need);
+    match lowest_larger {
+        Some(larger) if best_value != need && pool[larger].effective <= best_value => Some(vec![larger]),
+        _ => Some(best),
+    }
+}
+
+/// Randomized search of subset of `indices` with the smallest value not less than `need`
+fn approximate_best_subset(pool: &[Candidate], indices: &[usize], need: u64) -> (u64, Vec<usize>) {
+    let mut rng = thread_rng();
+    let mut indices = indices.to_vec();
+    indices.shuffle(&mut rng);
+
+    let mut best_value = indices.iter().map(|&i| pool[i].effective).sum::<u64>();
+    let mut best = vec![true; indices.len()];
+
+    for _ in 0..KNAPSACK_ITERATIONS {
+        if best_value == need { break; }
+        let mut included = vec![false; indices.len()];
+        let mut value = 0;
+        let mut reached = false;
+        for pass in 0..2 {
+            if reached { break; }
+            for (n, &i) in indices.iter().enumerate() {
+                // first pass picks randomly, second pass fills up with everything left
+                let pick = if pass == 0 { rng.gen::<bool>() } else { !included[n] };
+                if !pick || included[n] { continue; }
+                value += pool[i].effective;
+                included[n] = true;
+                if value >= need {
+                    reached = true;
+                    if value < best_value {
+                        best_value = value;
+                        best = included.clone();
+                    }
+                    value -= pool[i].effective;
+                    included[n] = false;
+                }
+            }
+        }
+    }
+
+    let best = indices.iter().zip(best).filter_map(|(&i, x)| x.then_some(i)).collect();
+    (best_value, best)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::hashes::Hash;
+
+    use crate::{minter::{testing::status, tx::{DUST_LIMIT, P2PKH_INPUT_VSIZE, P2PKH_OUTPUT_VSIZE}, utxo::UtxoData}, wallet::AddressType, FeeRate};
+
+    use super::*;
+
+    const BASE_VSIZE: usize = 44;
+
+    fn candidates(values: &[u64]) -> Vec<(String, UtxoData)> {
+        values.iter().enumerate().map(|(i, &value)| ("address".to_owned(), UtxoData {
+            txid: bitcoin::Txid::all_zeros(),
+            vout: i as u32,
+            status: status(true),
+            value,
+            ty: AddressType::Utxo,
+            inscription_meta: None,
+            owner: None,
+        })).collect()
+    }
+
+    /// Params at 1 nook/vB, so fees equal sizes
+    fn params(target: u64, existing: u64) -> CoinSelectionParams {
+        CoinSelectionParams { target, existing, base_vsize: BASE_VSIZE, fee_rate: FeeRate::try_from(1.0).unwrap() }
+    }
+
+    /// Value of utxo which has `effective` value left after paying for its input
+    fn value(effective: u64) -> u64 {
+        effective + P2PKH_INPUT_VSIZE as u64
+    }
+
+    fn selected_values(selection: &Selection) -> Vec<u64> {
+        let mut values = selection.utxo.iter().map(|(_, x)| x.value).collect::<Vec<_>>();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn exact_match_has_no_change() {
+        let target = 50_000 - BASE_VSIZE as u64;
+        let pool = candidates(&[value(70_000), value(30_000), value(20_000), value(5_000)]);
+        let selection = select_coins(pool, &params(target, 0)).unwrap();
+
+        assert_eq!(selected_values(&selection), vec![value(20_000), value(30_000)]);
+        assert_eq!(selection.change, 0);
+        assert_eq!(selection.fee, BASE_VSIZE as u64 + 2 * P2PKH_INPUT_VSIZE as u64);
+        assert_eq!(selected_values(&selection).iter().sum::<u64>(), target + selection.fee);
+    }
+
+    #[test]
+    fn excess_below_change_cost_goes_to_fee() {
+        let need = 10_000;
+        let excess = P2PKH_OUTPUT_VSIZE as u64 + DUST_LIMIT - 1;
+        let utxo = value(need + excess);
+        let selection = select_coins(candidates(&[utxo]), &params(need - BASE_VSIZE as u64, 0)).unwrap();
+
+        assert_eq!(selection.change, 0);
+        assert_eq!(selection.fee, BASE_VSIZE as u64 + P2PKH_INPUT_VSIZE as u64 + excess);
+        assert_eq!(need - BASE_VSIZE as u64 + selection.fee, utxo);
+    }
+
+    #[test]
+    fn excess_of_change_cost_makes_change() {
+        let need = 10_000;
+        let excess = P2PKH_OUTPUT_VSIZE as u64 + DUST_LIMIT;
+        let utxo = value(need + excess);
+        let selection = select_coins(candidates(&[utxo]), &params(need - BASE_VSIZE as u64, 0)).unwrap();
+
+        assert_eq!(selection.change, DUST_LIMIT);
+        assert_eq!(selection.fee, (BASE_VSIZE + P2PKH_INPUT_VSIZE + P2PKH_OUTPUT_VSIZE) as u64);
+        assert_eq!(need - BASE_VSIZE as u64 + selection.fee + selection.change, utxo);
+    }
+
+    #[test]
+    fn smallest_larger_candidate_is_preferred() {
+        let pool = candidates(&[value(1_000_000), value(30_000), value(4_000), value(3_000)]);
+        let selection = select_coins(pool, &params(10_000, 0)).unwrap();
+
+        assert_eq!(selected_values(&selection), vec![value(30_000)]);
+        assert_eq!(selection.change, 30_000 - 10_000 - (BASE_VSIZE + P2PKH_OUTPUT_VSIZE) as u64);
+    }
+
+    #[test]
+    fn insufficient_funds_reports_shortfall() {
+        // the last one is worth less than its input
+        let pool = candidates(&[value(2_000), value(1_000), 100]);
+        let err = select_coins(pool, &params(10_000 - BASE_VSIZE as u64, 0)).unwrap_err();
+
+        assert_eq!(err, CoinSelectionError::InsufficientFunds { needed: Amount::from_sat(10_000), available: Amount::from_sat(3_000) });
+        assert_eq!(err.shortfall(), Amount::from_sat(7_000));
+    }
+
+    #[test]
+    fn existing_inputs_paying_everything_select_nothing() {
+        let target = 100_000;
+        let base_fee = BASE_VSIZE as u64;
+
+        let selection = select_coins(candidates(&[value(50_000)]), &params(target, target + base_fee)).unwrap();
+        assert!(selection.utxo.is_empty());
+        assert_eq!((selection.fee, selection.change), (base_fee, 0));
+
+        // surplus too small for change output is left to fee
+        let surplus = P2PKH_OUTPUT_VSIZE as u64 + DUST_LIMIT - 1;
+        let selection = select_coins(candidates(&[]), &params(target, target + base_fee + surplus)).unwrap();
+        assert_eq!((selection.fee, selection.change), (base_fee + surplus, 0));
+
+        let surplus = 50_000;
+        let selection = select_coins(candidates(&[]), &params(target, target + base_fee + surplus)).unwrap();
+        assert!(selection.utxo.is_empty());
+        assert_eq!((selection.fee, selection.change), (base_fee + P2PKH_OUTPUT_VSIZE as u64, surplus - P2PKH_OUTPUT_VSIZE as u64));
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::Context;
+use anyhow::bail;
+use bitcoin::{secp256k1::{Secp256k1, SecretKey}, util::bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, ExtendedPubKey}};
+
+use crate::{chain::Chain, wallet::{AddressType, Wallet, WalletAddressData, WalletSecrets}};
+
+use super::Minter;
+
+pub struct DerivedAddress {
+    pub address: bitcoin::Address,
+    /// None for address derived from xpub of watch-only wallet
+    pub private: Option<SecretKey>,
+    pub path: DerivationPath,
+}
+
+fn index_key(wallet: &str, ty: AddressType) -> String {
+    format!("{wallet}/{}", ty.name())
+}
+
+/// BIP44 account path `m/44'/{coin_type}'/0'`, parent of utxo and ord chains
+pub fn account_path(network: Chain) -> DerivationPath {
+    DerivationPath::from(vec![
+        ChildNumber::Hardened { index: 44 },
+        ChildNumber::Hardened { index: network.coin_type() },
+        ChildNumber::Hardened { index: 0 },
+    ])
+}
+
+/// BIP44 path `m/44'/{coin_type}'/0'/{chain}/{index}`
+pub fn derivation_path(network: Chain, ty: AddressType, index: u32) -> DerivationPath {
+    account_path(network).extend([ChildNumber::Normal { index: ty.chain() }, ChildNumber::Normal { index }])
+}
+
+impl Minter {
+    /// Derive p2pkh address `index` of wallet chain used for `ty` addresses on wallet network.
+    /// Addresses of watch-only wallet are derived from its xpub and have no private key
+    pub fn derive_address(&self, wallet: &Wallet, ty: AddressType, index: u32) -> anyhow::Result<DerivedAddress> {
+        let secp = Secp256k1::new();
+        let path = derivation_path(wallet.network, ty, index);
+
+        let (public_key, private) = match &wallet.secrets {
+            WalletSecrets::WatchOnly { xpub: Some(xpub) } => {
+                let derived_key = xpub.derive_pub(&secp, &[ChildNumber::Normal { index: ty.chain() }, ChildNumber::Normal { index }])
+                    .context("Failed to derive a key")?;
+                (derived_key.public_key, None)
+            }
+            WalletSecrets::WatchOnly { xpub: None } => bail!("Wallet #{} is watch-only without xpub. Import its addresses with `wallet import-addresses`", wallet.name),
+            _ => {
+                let seed = self.wallet_seed(wallet)?;
+                let master_key = ExtendedPrivKey::new_master(wallet.network.network(), &seed).context("Failed to create master key")?;
+                let derived_key = master_key.derive_priv(&secp, &path).context("Failed to derive a key")?;
+                (derived_key.private_key.public_key(&secp), Some(derived_key.private_key))
+            }
+        };
+        Ok(DerivedAddress {
+            address: bitcoin::Address::p2pkh(&bitcoin::PublicKey::new(public_key), wallet.network.network()),
+            private,
+            path,
+        })
+    }
+
+    /// Extended public key of wallet account, for watch-only copies of wallet. Encrypted wallet must be unlocked
+    pub fn account_xpub(&self, wallet: &Wallet) -> anyhow::Result<ExtendedPubKey> {
+        match &wallet.secrets {
+            WalletSecrets::WatchOnly { xpub: Some(xpub) } => return Ok(*xpub),
+            WalletSecrets::WatchOnly { xpub: None } => bail!("Wallet #{} is watch-only without xpub", wallet.name),
+            _ => {}
+        }
+        let secp = Secp256k1::new();
+        let seed = self.wallet_seed(wallet)?;
+        let master_key = ExtendedPrivKey::new_master(wallet.network.network(), &seed).context("Failed to create master key")?;
+        let account_key = master_key.derive_priv(&secp, &account_path(wallet.network)).context("Failed to derive account key")?;
+        Ok(ExtendedPubKey::from_priv(&secp, &account_key))
+    }
+
+    /// Index of next address of `ty` chain. Wallets created before counters existed derived
+    /// indices from the number of saved addresses, so that number is used as a safe start
+    pub fn next_index(&self, wallet: &str, ty: AddressType) -> anyhow::Result<u32> {
+        if let Some(index) = self.db.get::<u32>(&self.tables.indices.table()?, index_key(wallet, ty)).context("Failed to get derivation index")? {
+            return Ok(index);
+        }
+        let count = self.addresses(wallet)?.count() as u32;
+        let derived = self.addresses(wallet)?
+            .filter(|(_, x)| x.ty == ty)
+            .filter_map(|(_, x)| match x.derivation?.into_iter().last()? {
+                ChildNumber::Normal { index } => Some(index + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+        Ok(count.max(derived))
+    }
+
+    /// Make sure next address of `ty` chain has index after `index`
+    pub fn advance_index(&self, wallet: &str, ty: AddressType, index: u32) -> anyhow::Result<()> {
+        if self.next_index(wallet, ty)? > index { return Ok(()) }
+        self.db.set(&self.tables.indices.table()?, index_key(wallet, ty), &(index + 1)).context("Failed to save derivation index")
+    }
+
+    /// Derive and save next unused address of `ty` chain. Encrypted wallet must be unlocked
+    pub fn receive_address(&self, wallet: &str, ty: AddressType) -> anyhow::Result<bitcoin::Address> {
+        let wallet_data = self.get_wallet(wallet)?.context("Wallet not found")?;
+
+        let mut index = self.next_index(wallet, ty)?;
+        let derived = loop {
+            let derived = self.derive_address(&wallet_data, ty, index)?;
+            // addresses saved by restore or derive command must not be given out again
+            if self.get_address(wallet, &derived.address.to_string())?.is_none() { break derived; }
+            index += 1;
+        };
+        debug!("Derived {} address {} at {}", ty.name(), derived.address, derived.path);
+
+        self.push_address(&derived.address.to_string(), &WalletAddressData::derived(derived.private, ty, derived.path), wallet)?;
+        self.advance_index(wallet, ty, index)?;
+        Ok(derived.address)
+    }
+}
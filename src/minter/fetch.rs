@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use anyhow::{bail, Context};
+use indicatif::ProgressStyle;
+use itertools::Itertools;
+use tokio::{sync::Semaphore, task::JoinSet};
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+
+use super::{backend::{BackendFuture, ChainBackend}, Minter};
+
+/// Requests to chain backend running at the same time
+pub const FETCH_CONCURRENCY: usize = 8;
+
+impl Minter {
+    /// Run `request` for every address, at most `FETCH_CONCURRENCY` at once, showing progress of getting `what`.
+    /// Every request is finished before failing with errors of all failed addresses. Results keep order of `addresses`
+    pub async fn fetch_addresses<T: Send + 'static>(
+        &self,
+        what: &str,
+        addresses: Vec<String>,
+        request: impl Fn(Arc<dyn ChainBackend>, String) -> BackendFuture<'static, T>,
+    ) -> anyhow::Result<Vec<(String, T)>> {
+        let total = addresses.len();
+        let span = info_span!("fetch");
+        span.pb_set_style(&ProgressStyle::with_template("{spinner} {msg} {wide_bar} {pos}/{len}").context("Invalid progress template")?);
+        span.pb_set_length(total as u64);
+        span.pb_set_message(&format!("Getting {what}"));
+        span.pb_start();
+
+        let semaphore = Arc::new(Semaphore::new(FETCH_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for (i, address) in addresses.into_iter().enumerate() {
+            let semaphore = semaphore.clone();
+            let request = request(self.backend.clone(), address.clone());
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                (i, address, request.await)
+            });
+        }
+
+        let mut fetched = vec![];
+        let mut failed = vec![];
+        while let Some(task) = tasks.join_next().await {
+            let (i, address, result) = task.context("Fetch task panicked")?;
+            span.pb_inc(1);
+            match result {
+                Ok(x) => fetched.push((i, address, x)),
+                Err(err) => failed.push((i, address, err)),
+            }
+        }
+
+        if !failed.is_empty() {
+            let count = failed.len();
+            let errors = failed.into_iter()
+                .sorted_by_key(|x| x.0)
+                .map(|(_, address, err)| format!("{address}: {err:#}"))
+                .join("\n");
+            bail!("Failed to get {what} of {count} of {total} addresses:\n{errors}");
+        }
+        Ok(fetched.into_iter().sorted_by_key(|x| x.0).map(|(_, address, x)| (address, x)).collect())
+    }
+}
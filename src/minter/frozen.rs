@@ -0,0 +1,55 @@
+use std::{collections::HashSet, str::{from_utf8, FromStr}};
+
+use anyhow::{bail, Context};
+
+use super::{audit::AuditAction, utxo::UtxoData, Minter};
+
+fn frozen_key(wallet: &str, outpoint: &bitcoin::OutPoint) -> Vec<u8> {
+    format!("{wallet}/{outpoint}").into_bytes()
+}
+
+impl Minter {
+    /// Forbid spending of `outpoint` by coin selection
+    pub fn freeze_outpoint(&self, wallet: &str, outpoint: &bitcoin::OutPoint) -> anyhow::Result<()> {
+        self.db.set(&self.tables.frozen.table()?, frozen_key(wallet, outpoint), &()).context("Failed to freeze outpoint")?;
+        self.audit(wallet, AuditAction::Freeze, None, format!("Frozen {outpoint}"));
+        info!("Frozen {outpoint} in #{wallet}");
+        Ok(())
+    }
+
+    pub fn unfreeze_outpoint(&self, wallet: &str, outpoint: &bitcoin::OutPoint) -> anyhow::Result<()> {
+        let key = frozen_key(wallet, outpoint);
+        if !self.db.contains(&self.tables.frozen.table()?, &key)? {
+            bail!("Outpoint {outpoint} is not frozen in #{wallet}");
+        }
+        self.db.remove(&self.tables.frozen.table()?, &key).context("Failed to unfreeze outpoint")?;
+        self.audit(wallet, AuditAction::Unfreeze, None, format!("Unfrozen {outpoint}"));
+        info!("Unfrozen {outpoint} in #{wallet}");
+        Ok(())
+    }
+
+    pub fn frozen_outpoints(&self, wallet: &str) -> anyhow::Result<HashSet<bitcoin::OutPoint>> {
+        let mut prefix = wallet.to_owned();
+        prefix.push('/');
+
+        let frozen = self.db
+            .iterate(&self.tables.frozen.table()?, prefix.into_bytes())
+            .context("Failed to query frozen outpoints")?
+            .filter_map(|(key,_)| {
+                let outpoint = from_utf8(&key).ok()
+                    .and_then(|x| x.split('/').next_back())
+                    .and_then(|x| bitcoin::OutPoint::from_str(x).ok());
+                if outpoint.is_none() {
+                    error!("Found invalid frozen outpoint. Skipping");
+                }
+                outpoint
+            })
+            .collect();
+        Ok(frozen)
+    }
+
+    /// Check if utxo can be spent as fee or cardinal input: it carries no inscription and is not frozen
+    pub fn is_spendable(utxo: &UtxoData, frozen: &HashSet<bitcoin::OutPoint>) -> bool {
+        utxo.inscription_meta.is_none() && !frozen.contains(&bitcoin::OutPoint { txid: utxo.txid, vout: utxo.vout })
+    }
+}
@@ -0,0 +1,195 @@
+use std::{collections::{HashMap, HashSet}, sync::Arc};
+
+use anyhow::Context;
+use itertools::Itertools;
+
+use crate::wallet::AddressType;
+
+use super::{backend::{ChainBackend, ChainTx, CHAIN_TXS_PAGE}, Minter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TxKind {
+    Receive,
+    Send,
+    /// All outputs go back to wallet
+    SelfTransfer,
+}
+
+/// Net effect of transaction for wallet, in sats
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TxEffect {
+    pub kind: TxKind,
+    /// Value of outputs to wallet addresses
+    pub received: u64,
+    /// Value of outputs to other addresses, if wallet funded transaction
+    pub sent: u64,
+    /// Fee, if wallet funded transaction
+    pub fee: Option<u64>,
+    /// Change of wallet balance
+    pub net: i64,
+    /// Transaction spends from or pays to ord address of wallet
+    pub inscription_moved: bool,
+}
+
+// `{wallet}/tx/{txid}` keys hold transactions, `{wallet}/synced/{address}` mark addresses with complete history
+fn tx_key(wallet: &str, txid: &bitcoin::Txid) -> Vec<u8> {
+    format!("{wallet}/tx/{txid}").into_bytes()
+}
+fn synced_key(wallet: &str, address: &str) -> Vec<u8> {
+    format!("{wallet}/synced/{address}").into_bytes()
+}
+
+/// Get history of `address` page by page, until it is complete or reaches confirmed transaction from `known`
+async fn address_history(backend: &dyn ChainBackend, address: &str, known: &HashSet<bitcoin::Txid>) -> anyhow::Result<Vec<ChainTx>> {
+    let mut txs = vec![];
+    let mut last_seen = None;
+    loop {
+        let page = backend.address_txs(address, last_seen).await?;
+        let confirmed = page.iter().filter(|x| x.status.confirmed).map(|x| x.txid).collect_vec();
+        txs.extend(page);
+        // history is newest first, so older transactions are already saved
+        if confirmed.len() < CHAIN_TXS_PAGE || confirmed.iter().any(|x| known.contains(x)) {
+            return Ok(txs);
+        }
+        last_seen = confirmed.last().copied();
+    }
+}
+
+impl Minter {
+    /// Saved transactions of wallet, oldest first. Unconfirmed transactions go last
+    pub fn get_transactions(&self, wallet: &str) -> anyhow::Result<Vec<ChainTx>> {
+        let txs = self.db.iterate(&self.tables.transactions.table()?, format!("{wallet}/tx/").into_bytes())
+            .context("Failed to get transactions")?
+            .filter_map(|(_,v)| {
+                let Ok(tx) = bincode::deserialize::<ChainTx>(&v) else {
+                    error!("Invalid transaction data");
+                    return None;
+                };
+                Some(tx)
+            })
+            .sorted_by_key(|x| (!x.status.confirmed, x.status.block_height, x.status.block_time))
+            .collect();
+        Ok(txs)
+    }
+
+    /// Get new transactions of all wallet addresses from chain backend and save them.
+    /// Saved unconfirmed transactions which are no longer reported are removed
+    pub async fn fetch_transactions(&self, wallet: &str) -> anyhow::Result<Vec<ChainTx>> {
+        debug!("Fetching transactions");
+        let saved = self.get_transactions(wallet)?;
+        let known = Arc::new(saved.iter().filter(|x| x.status.confirmed).map(|x| x.txid).collect::<HashSet<_>>());
+        let no_known = Arc::new(HashSet::new());
+
+        let addresses = self.addresses(wallet)?.map(|x| x.0).collect_vec();
+        let mut synced = HashSet::new();
+        for address in &addresses {
+            if self.db.contains(&self.tables.transactions.table()?, synced_key(wallet, address))? {
+                synced.insert(address.clone());
+            }
+        }
+
+        let fetched = self.fetch_addresses("transactions", addresses.clone(), |backend, address| {
+            // address without complete history must be fetched to the end, even if it shares transactions with others
+            let known = if synced.contains(&address) { known.clone() } else { no_known.clone() };
+            Box::pin(async move { address_history(&*backend, &address, &known).await })
+        }).await?;
+
+        let fetched = fetched.into_iter()
+            .flat_map(|(_, txs)| txs)
+            .map(|x| (x.txid, x))
+            .collect::<HashMap<_,_>>();
+        let dropped = saved.iter()
+            .filter(|x| !x.status.confirmed && !fetched.contains_key(&x.txid))
+            .map(|x| tx_key(wallet, &x.txid))
+            .collect_vec();
+        debug!("Got {} transactions of {wallet}, {} unconfirmed dropped", fetched.len(), dropped.len());
+
+        self.db.write_many(&self.tables.transactions.table()?, fetched.values().map(|x| (tx_key(wallet, &x.txid), x)), dropped)
+            .context("Failed to save transactions")?;
+        // marked after transactions are saved, so failed save gets whole history again
+        self.db.set_many(&self.tables.transactions.table()?, addresses.iter().map(|x| (synced_key(wallet, x), ())))
+            .context("Failed to mark synced addresses")?;
+
+        self.get_transactions(wallet)
+    }
+
+    /// Address types of all wallet addresses, to get `tx_effect` of many transactions
+    pub fn address_types(&self, wallet: &str) -> anyhow::Result<HashMap<String, AddressType>> {
+        Ok(self.addresses(wallet)?.map(|(addr, data)| (addr, data.ty)).collect())
+    }
+}
+
+/// Net effect of `tx` for wallet with `addresses` from `Minter::address_types`
+pub fn tx_effect(addresses: &HashMap<String, AddressType>, tx: &ChainTx) -> TxEffect {
+    let owner = |address: Option<&String>| address.and_then(|x| addresses.get(x)).copied();
+
+    let mut spent = 0;
+    let mut received = 0;
+    let mut sent = 0;
+    let mut inscription_moved = false;
+    for prevout in tx.inputs.iter().filter_map(|x| x.prevout.as_ref()) {
+        let Some(ty) = owner(prevout.address.as_ref()) else { continue };
+        spent += prevout.value;
+        inscription_moved |= ty == AddressType::Ord;
+    }
+    for output in &tx.outputs {
+        match owner(output.address.as_ref()) {
+            Some(ty) => {
+                received += output.value;
+                inscription_moved |= ty == AddressType::Ord;
+            }
+            None => sent += output.value,
+        }
+    }
+
+    let funded = spent > 0;
+    let kind = match (funded, sent) {
+        (false, _) => TxKind::Receive,
+        (true, 0) => TxKind::SelfTransfer,
+        (true, _) => TxKind::Send,
+    };
+    TxEffect {
+        kind,
+        received,
+        sent: if funded { sent } else { 0 },
+        fee: funded.then_some(tx.fee),
+        net: received as i64 - spent as i64,
+        inscription_moved,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::minter::{backend::{TxInput, TxOutput}, testing::status};
+
+    use super::*;
+
+    fn output(address: &str, value: u64) -> TxOutput {
+        TxOutput { address: Some(address.to_owned()), value }
+    }
+
+    fn chain_tx(inputs: Vec<TxOutput>, outputs: Vec<TxOutput>, fee: u64) -> ChainTx {
+        ChainTx {
+            txid: bitcoin::hashes::Hash::all_zeros(),
+            inputs: inputs.into_iter().map(|x| TxInput { previous_output: bitcoin::OutPoint::null(), prevout: Some(x) }).collect(),
+            outputs,
+            fee,
+            status: status(false),
+        }
+    }
+
+    #[test]
+    fn tx_effect_of_wallet_transactions() {
+        let addresses = HashMap::from([("utxo".to_owned(), AddressType::Utxo), ("ord".to_owned(), AddressType::Ord)]);
+
+        let send = tx_effect(&addresses, &chain_tx(vec![output("utxo", 10_000)], vec![output("other", 6_000), output("utxo", 3_000)], 1_000));
+        assert_eq!((send.kind, send.received, send.sent, send.fee, send.net, send.inscription_moved), (TxKind::Send, 3_000, 6_000, Some(1_000), -7_000, false));
+
+        let receive = tx_effect(&addresses, &chain_tx(vec![output("other", 10_000)], vec![output("ord", 1_000), output("other", 8_000)], 1_000));
+        assert_eq!((receive.kind, receive.received, receive.sent, receive.fee, receive.net, receive.inscription_moved), (TxKind::Receive, 1_000, 0, None, 1_000, true));
+
+        let transfer = tx_effect(&addresses, &chain_tx(vec![output("utxo", 10_000)], vec![output("utxo", 9_000)], 1_000));
+        assert_eq!((transfer.kind, transfer.net), (TxKind::SelfTransfer, -1_000));
+    }
+}
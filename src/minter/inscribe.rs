@@ -1,169 +1,354 @@
-use std::collections::{HashMap, VecDeque};
+use std::{collections::{HashMap, VecDeque}, path::Path, str::FromStr};
 
 use anyhow::{bail, Context};
-use bitcoin::{blockdata::{opcodes, script::{self, Instruction}}, secp256k1::Secp256k1};
-use itertools::Itertools;
+use bitcoin::{blockdata::{opcodes, script::{self, Instruction}}, secp256k1::{Secp256k1, SecretKey}};
 
-use super::{utxo::{InscriptionId, UtxoData}, Minter};
+use crate::{wallet::AddressType, FeeRate};
+
+use super::{coin_select::CoinSelectionParams, tx::{sign_input, sign_p2pkh_input, DUST_LIMIT}, utxo::{InscriptionId, UtxoData}, Minter};
+
+const PROTOCOL_ID: &[u8] = b"ord";
+const CHUNK_SIZE: usize = 240;
+const MAX_PAYLOAD_SIZE: usize = 1500;
+/// Value of p2sh outputs carrying the inscription and of the final inscription output
+const INSCRIPTION_SAT: u64 = 100_000;
 
 pub struct Inscription {
     body: Option<Vec<u8>>,
     content_type: Option<Vec<u8>>,
 }
 
-struct CreateInscriptionTx {
-    inscription: Inscription,
-    utxo_in: Vec<(String, UtxoData)>,
-    dest: bitcoin::Address,
-    inscriptions: HashMap<bitcoin::OutPoint, InscriptionId>,
-    change_privk: bitcoin::PrivateKey,
-    change_address: bitcoin::Address,
+impl Inscription {
+    pub fn new(content_type: Option<Vec<u8>>, body: Option<Vec<u8>>) -> Self {
+        Self { body, content_type }
+    }
+
+    /// Read inscription from file. Content type is guessed from extension if not given
+    pub fn from_file(path: impl AsRef<Path>, content_type: Option<String>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let body = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let content_type = match content_type {
+            Some(x) => x,
+            None => {
+                let extension = path.extension().and_then(|x| x.to_str()).unwrap_or_default().to_lowercase();
+                content_type_for_extension(&extension)
+                    .with_context(|| format!("Unknown content type of '{}'. Set it with --content-type", path.display()))?
+                    .to_owned()
+            }
+        };
+
+        Ok(Self::new(Some(content_type.into_bytes()), Some(body)))
+    }
+
+    pub fn body(&self) -> Option<&[u8]> { self.body.as_deref() }
+    pub fn content_type(&self) -> Option<&[u8]> { self.content_type.as_deref() }
+
+    /// Full inscription script. It is split between transactions of the chain
+    fn script(&self) -> anyhow::Result<bitcoin::Script> {
+        let content_type = self.content_type.as_deref().context("Inscription has no content type")?;
+        let body = self.body.as_deref().unwrap_or_default();
+        if body.is_empty() {
+            bail!("Inscription body is empty");
+        }
+
+        let parts = body.chunks(CHUNK_SIZE).collect::<Vec<_>>();
+
+        let mut builder = script::Builder::new()
+            .push_slice(PROTOCOL_ID)
+            .push_int(parts.len() as i64)
+            .push_slice(content_type);
+
+        for (n, part) in parts.iter().enumerate() {
+            builder = builder
+                .push_int(parts.len() as i64 - n as i64 - 1)
+                .push_slice(part);
+        }
+        Ok(builder.into_script())
+    }
+}
+
+fn content_type_for_extension(extension: &str) -> Option<&'static str> {
+    Some(match extension {
+        "txt" => "text/plain;charset=utf-8",
+        "html" | "htm" => "text/html;charset=utf-8",
+        "css" => "text/css",
+        "js" => "application/javascript",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "pdf" => "application/pdf",
+        "glb" => "model/gltf-binary",
+        _ => return None,
+    })
+}
+
+pub struct CreateInscriptionTx {
+    pub inscription: Inscription,
+    pub utxo_in: Vec<(String, UtxoData)>,
+    pub dest: bitcoin::Address,
+    pub inscriptions: HashMap<bitcoin::OutPoint, InscriptionId>,
+    pub change_privk: bitcoin::PrivateKey,
+    pub change_address: bitcoin::Address,
+    pub fee_rate: FeeRate,
 }
 
-fn instruction_size(instr: &script::Instruction) -> usize {
+/// Cardinal output used to fund the next transaction of the chain
+struct FundingCoin {
+    outpoint: bitcoin::OutPoint,
+    value: u64,
+    private: SecretKey,
+}
+
+/// Serialized size of instruction inside a script
+fn instruction_size(instr: &Instruction) -> usize {
+    match instr {
+        Instruction::PushBytes(x) if x.len() < opcodes::all::OP_PUSHDATA1.to_u8() as usize => 1 + x.len(),
+        Instruction::PushBytes(x) if x.len() <= 0xff => 2 + x.len(),
+        Instruction::PushBytes(x) if x.len() <= 0xffff => 3 + x.len(),
+        Instruction::PushBytes(x) => 5 + x.len(),
+        Instruction::Op(_) => 1,
+    }
+}
+
+fn push_instruction(builder: script::Builder, instr: &Instruction) -> script::Builder {
     match instr {
-        script::Instruction::PushBytes(x) => x.len(),
-        script::Instruction::Op(_) => 1,
+        Instruction::PushBytes(x) => builder.push_slice(x),
+        Instruction::Op(x) => builder.push_opcode(*x),
     }
 }
 
+/// Unlock script of p2sh output locked with `lock`: inscription part, signature and redeem script
+fn p2sh_unlock(partial: &[Instruction], sig: &[u8], lock: &bitcoin::Script) -> bitcoin::Script {
+    partial.iter()
+        .fold(script::Builder::new(), push_instruction)
+        .push_slice(sig)
+        .push_slice(lock.as_bytes())
+        .into_script()
+}
+
 impl Minter {
-    // fn create_inscription_tx(info: CreateInscriptionTx) -> anyhow::Result<Vec<bitcoin::Transaction>> {
-        // // get outpoint
-        // let outpoint = info.utxo_in
-        //     .iter()
-        //     .map(|(_,x)| bitcoin::OutPoint { txid: x.txid, vout: x.vout })
-        //     .find(|x| !info.inscriptions.contains_key(x))
-        //     .context("No cardinal utxo's found in wallet")?;
-
-        // // check if any of inscriptions is already inscribed
-        // for (i_outpoint, i_id) in &info.inscriptions {
-        //     if i_outpoint == &outpoint {
-        //         bail!("Inscription {i_id:?} is already inscribed on {i_outpoint}");
-        //     }
-        // }
-
-        // const PROTOCOL_ID: &[u8] = b"ord";
-        // const CHUNK_SIZE: usize = 240;
-        // const DUST_SAT: u64 = 10_000;
-        
-        // // build inscription script
-        // let shit = vec![];
-        // let mut parts = info.inscription.body
-        //     .as_ref()
-        //     .unwrap_or(&shit) //todo: what if we have None?
-        //     .chunks(CHUNK_SIZE)
-        //     .enumerate();
-
-        // let parts_count = parts.by_ref().count();
-
-        // let mut builder = script::Builder::new()
-        //     .push_slice(PROTOCOL_ID)
-        //     .push_int(parts_count as i64)
-        //     .push_slice(info.inscription.content_type.as_ref().unwrap());
-
-        // for (n,part) in parts {
-        //     builder = builder
-        //         .push_int(parts_count as i64 - n as i64 - 1)
-        //         .push_slice(part);
-        // }
-        // let script = builder.into_script();
-
-        // // make transactions from instructions
-        // let mut transactions = vec![];
-        // let mut instructions = VecDeque::from_iter(script.instructions().flatten());
-        // let mut p2sh_input: Option<bitcoin::TxIn> = None;
-        // let mut last_partial: Option<bitcoin::Script> = None;
-        // let mut last_lock: Option<bitcoin::Script> = None;
-
-        // let mut the_thing = vec![]; // please, do not ask me what it is
-        // while !instructions.is_empty() {
-        //     the_thing.clear();
-        //     let mut chunk_bytes_len = 0;
-
-        //     if transactions.is_empty() {
-        //         let i = instructions.pop_front().unwrap();
-        //         chunk_bytes_len += instruction_size(&i);
-        //         the_thing.push(i);
-        //     }
-
-        //     while chunk_bytes_len <= 1500 && !instructions.is_empty() {
-        //         let i = instructions.pop_front().unwrap();
-        //         chunk_bytes_len += instruction_size(&i);
-        //         the_thing.push(i);
-
-        //         let i = instructions.pop_front().unwrap();
-        //         chunk_bytes_len += instruction_size(&i);
-        //         the_thing.push(i);
-        //     }
-
-        //     if chunk_bytes_len > 1500 {
-        //         instructions.push_front(the_thing.pop().unwrap());
-        //         instructions.push_front(the_thing.pop().unwrap());
-        //     }
-
-        //     // build lockscript
-        //     let secp = Secp256k1::new(); //todo: use lazy_static
-
-        //     let mut lock = script::Builder::new()
-        //         .push_slice(&info.change_privk.public_key(&secp).to_bytes())
-        //         .push_opcode(opcodes::all::OP_CHECKSIGVERIFY);
-        //     for _ in &the_thing {
-        //         lock = lock.push_opcode(opcodes::all::OP_DROP)
-        //     }
-        //     lock = lock.push_opcode(opcodes::OP_TRUE);
-
-        //     let p2sh_out = bitcoin::TxOut {
-        //         value: DUST_SAT,
-        //         script_pubkey: lock.into_script().to_p2sh(),
-        //     };
-        //     let change = bitcoin::TxOut {
-        //         value: 0,
-        //         script_pubkey: info.change_address.script_pubkey(),
-        //     };
-        //     let mut tx = bitcoin::Transaction {
-        //         version: 1,
-        //         lock_time: bitcoin::PackedLockTime::ZERO,
-        //         input: vec![],
-        //         output: vec![p2sh_out, change],
-        //     };
-
-        //     if let Some(ref p2sh_input) = p2sh_input {
-        //         tx.input.push(p2sh_input.clone());
-        //     }
-
-        //     // estimate fee
-        //     let unlock_size = if let (Some(last_partial), Some(last_lock)) = (&last_partial, &last_lock) {
-        //         let mut unlock = script::Builder::new();
-        //         for instruction in last_partial.instructions() {
-        //             match instruction {
-        //                 Ok(Instruction::Op(x)) => unlock = unlock.push_opcode(x),
-        //                 Ok(Instruction::PushBytes(x)) => unlock = unlock.push_slice(x),
-        //                 Err(e) => bail!("Invalid instruction: {e}"),
-        //             }
-        //         }
-        //         unlock
-        //             .push_slice(&[0;74])
-        //             .push_slice(last_lock.as_bytes())
-        //             .into_script()
-        //             .as_bytes()
-        //             .len()
-        //     } else { 0 };
-
-        //     let mut total_val = bitcoin::Amount::ZERO;
-
-
-        // }
-    
-        // Ok(transactions)
-        
-
-
-        // //todo: get satpoint
-        // //todo: check if one of inscriptions is inscribed
-        // //todo: build inscription script
-        // //todo: make transactions from instructions
-        // //todo: make final transaction
-        // //todo: calc fees
-    // }
-}
\ No newline at end of file
+    /// Build and sign the chain of transactions writing the inscription into p2sh unlock scripts.
+    /// Last transaction sends the inscribed sat to `dest` as output 0
+    pub fn create_inscription_tx(&self, wallet: &str, info: CreateInscriptionTx) -> anyhow::Result<Vec<bitcoin::Transaction>> {
+        let secp = Secp256k1::new();
+        let change_key = info.change_privk.inner;
+        let change_public = info.change_privk.public_key(&secp);
+
+        // check if any of inscriptions would be spent as a fee
+        for (_, utxo) in &info.utxo_in {
+            let outpoint = bitcoin::OutPoint { txid: utxo.txid, vout: utxo.vout };
+            if let Some(id) = info.inscriptions.get(&outpoint) {
+                bail!("Inscription {id:?} is already inscribed on {outpoint}");
+            }
+        }
+        if info.utxo_in.is_empty() {
+            bail!("No cardinal utxo's found in wallet");
+        }
+
+        let mut coins = info.utxo_in.iter()
+            .map(|(addr, utxo)| Ok(FundingCoin {
+                outpoint: bitcoin::OutPoint { txid: utxo.txid, vout: utxo.vout },
+                value: utxo.value,
+                private: self.get_private(wallet, addr)?,
+            }))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let script = info.inscription.script()?;
+        let mut instructions = script.instructions().collect::<Result<VecDeque<_>, _>>().context("Invalid inscription script")?;
+
+        let mut transactions = vec![];
+        let mut p2sh_input: Option<(bitcoin::OutPoint, Vec<Instruction>, bitcoin::Script)> = None;
+
+        while !instructions.is_empty() {
+            let mut partial = vec![];
+            let mut partial_size = 0;
+
+            // protocol id goes alone, then pairs of (part number, part)
+            if transactions.is_empty() {
+                let i = instructions.pop_front().unwrap();
+                partial_size += instruction_size(&i);
+                partial.push(i);
+            }
+
+            while partial_size <= MAX_PAYLOAD_SIZE && !instructions.is_empty() {
+                for _ in 0..2 {
+                    let i = instructions.pop_front().context("Inscription script is malformed")?;
+                    partial_size += instruction_size(&i);
+                    partial.push(i);
+                }
+            }
+
+            if partial_size > MAX_PAYLOAD_SIZE {
+                instructions.push_front(partial.pop().unwrap());
+                instructions.push_front(partial.pop().unwrap());
+            }
+
+            let mut lock = script::Builder::new()
+                .push_key(&change_public)
+                .push_opcode(opcodes::all::OP_CHECKSIGVERIFY);
+            for _ in &partial {
+                lock = lock.push_opcode(opcodes::all::OP_DROP);
+            }
+            let lock = lock.push_opcode(opcodes::OP_TRUE).into_script();
+
+            let p2sh_out = bitcoin::TxOut {
+                value: INSCRIPTION_SAT,
+                script_pubkey: lock.to_p2sh(),
+            };
+            let tx = self.fund_and_sign(&secp, &info, &mut coins, p2sh_input.take(), p2sh_out, &change_key)?;
+
+            p2sh_input = Some((bitcoin::OutPoint { txid: tx.txid(), vout: 0 }, partial, lock));
+            transactions.push(tx);
+        }
+
+        let dest_out = bitcoin::TxOut {
+            value: INSCRIPTION_SAT,
+            script_pubkey: info.dest.script_pubkey(),
+        };
+        let tx = self.fund_and_sign(&secp, &info, &mut coins, p2sh_input, dest_out, &change_key)?;
+        transactions.push(tx);
+
+        Ok(transactions)
+    }
+
+    /// Make transaction of the chain: [p2sh input, funding inputs] -> [out, change]
+    fn fund_and_sign(
+        &self,
+        secp: &Secp256k1<bitcoin::secp256k1::All>,
+        info: &CreateInscriptionTx,
+        coins: &mut Vec<FundingCoin>,
+        p2sh_input: Option<(bitcoin::OutPoint, Vec<Instruction>, bitcoin::Script)>,
+        out: bitcoin::TxOut,
+        change_key: &SecretKey,
+    ) -> anyhow::Result<bitcoin::Transaction> {
+        let mut input_value = coins.iter().map(|x| x.value).sum::<u64>();
+        let mut tx = bitcoin::Transaction {
+            version: 1,
+            lock_time: bitcoin::PackedLockTime::ZERO,
+            input: vec![],
+            output: vec![out, bitcoin::TxOut { value: 0, script_pubkey: info.change_address.script_pubkey() }],
+        };
+        if let Some((outpoint, _, _)) = &p2sh_input {
+            input_value += INSCRIPTION_SAT;
+            tx.input.push(new_input(*outpoint));
+        }
+        tx.input.extend(coins.iter().map(|x| new_input(x.outpoint)));
+
+        let sign = |tx: &mut bitcoin::Transaction| -> anyhow::Result<()> {
+            let offset = if let Some((_, partial, lock)) = &p2sh_input {
+                let sig = sign_input(secp, tx, 0, lock, change_key)?;
+                tx.input[0].script_sig = p2sh_unlock(partial, &sig.to_vec(), lock);
+                1
+            } else { 0 };
+            for (i, coin) in coins.iter().enumerate() {
+                sign_p2pkh_input(secp, tx, offset + i, &coin.private)?;
+            }
+            Ok(())
+        };
+
+        // sign once to know the real size, then sign again with the final change
+        sign(&mut tx)?;
+        let fee = info.fee_rate.fee(tx.size()).to_sat();
+        let Some(change) = input_value.checked_sub(tx.output[0].value + fee).filter(|x| *x >= DUST_LIMIT) else {
+            bail!("Not enough funds to inscribe: have {input_value}, need {} for output and fee", tx.output[0].value + fee + DUST_LIMIT);
+        };
+        tx.output[1].value = change;
+        sign(&mut tx)?;
+
+        *coins = vec![FundingCoin {
+            outpoint: bitcoin::OutPoint { txid: tx.txid(), vout: 1 },
+            value: change,
+            private: *change_key,
+        }];
+        Ok(tx)
+    }
+
+    /// Inscribe `inscription` to `dest` funding it with utxo-type addresses of the wallet
+    pub async fn inscribe(&self, wallet: &str, inscription: Inscription, dest: bitcoin::Address, fee_rate: FeeRate) -> anyhow::Result<(Vec<bitcoin::Transaction>, Vec<(String, UtxoData)>)> {
+        self.check_address(&dest)?;
+        self.check_can_sign(wallet)?;
+
+        // rough estimate: every byte of the script and ~500 bytes of every transaction
+        let script_len = inscription.script()?.len();
+        let tx_count = script_len / MAX_PAYLOAD_SIZE + 2;
+        let needed = fee_rate.fee(script_len + tx_count * 500).to_sat() + INSCRIPTION_SAT + DUST_LIMIT;
+
+        let utxo = self.gather_utxo(wallet, AddressType::Utxo, &CoinSelectionParams {
+            target: needed,
+            existing: 0,
+            base_vsize: 0,
+            fee_rate,
+        }).await.context("Failed to retrieve available utxo's for inscription")?.utxo;
+
+        let inscriptions = self.get_all_utxo(wallet, |_,_| true)?
+            .iter()
+            .filter_map(|(_, x)| x.inscription_meta.as_ref().map(|m| (bitcoin::OutPoint { txid: x.txid, vout: x.vout }, m.inscription_id.clone())))
+            .collect();
+
+        // change goes back to the first spent address, which is always utxo address
+        let change_address = bitcoin::Address::from_str(&utxo[0].0).context("Invalid change address")?;
+        let change_privk = bitcoin::PrivateKey::new(self.get_private(wallet, &utxo[0].0)?, change_address.network);
+
+        let transactions = self.create_inscription_tx(wallet, CreateInscriptionTx {
+            inscription,
+            utxo_in: utxo.clone(),
+            dest,
+            inscriptions,
+            change_privk,
+            change_address,
+            fee_rate,
+        })?;
+
+        Ok((transactions, utxo))
+    }
+}
+
+/// Txid of the first reveal transaction of chain made by `create_inscription_tx`. Indexers key inscription
+/// split between several transactions by it, with index 0
+pub fn reveal_txid(transactions: &[bitcoin::Transaction]) -> Option<bitcoin::Txid> {
+    transactions.get(1).map(|x| x.txid())
+}
+
+fn new_input(previous_output: bitcoin::OutPoint) -> bitcoin::TxIn {
+    bitcoin::TxIn {
+        previous_output,
+        script_sig: bitcoin::Script::new(),
+        sequence: bitcoin::Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: bitcoin::Witness::new(),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::minter::testing::{TestMinter, WALLET};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn chunked_inscription_is_identified_by_first_reveal() {
+        let test = TestMinter::new();
+        test.fund(AddressType::Utxo, 10_000_000, true);
+        let dest = test.minter.receive_address(WALLET, AddressType::Ord).unwrap();
+        let inscription = Inscription::new(Some(b"text/plain;charset=utf-8".to_vec()), Some(vec![b'x'; 4 * MAX_PAYLOAD_SIZE]));
+
+        let (transactions, _) = test.minter.inscribe(WALLET, inscription, dest.clone(), FeeRate::try_from(1.0).unwrap()).await.unwrap();
+        assert!(transactions.len() > 3, "{} transactions", transactions.len());
+
+        let reveal = &transactions[1];
+        assert_eq!(reveal_txid(&transactions), Some(reveal.txid()));
+        assert_eq!(reveal.input[0].previous_output, bitcoin::OutPoint { txid: transactions[0].txid(), vout: 0 });
+        let first_push = reveal.input[0].script_sig.instructions().next().unwrap().unwrap();
+        assert!(matches!(first_push, Instruction::PushBytes(x) if x == PROTOCOL_ID));
+        assert_eq!(transactions.last().unwrap().output[0].script_pubkey, dest.script_pubkey());
+    }
+}
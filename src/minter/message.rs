@@ -0,0 +1,28 @@
+use anyhow::{bail, Context};
+use bitcoin::{secp256k1::Secp256k1, util::misc::{bellscoin_signed_msg_hash, MessageSignature}};
+
+use super::{audit::AuditAction, Minter};
+
+impl Minter {
+    /// Sign `message` with key of p2pkh wallet address, proving its ownership. Encrypted wallet must be unlocked
+    pub fn sign_message(&self, wallet: &str, address: &bitcoin::Address, message: &str) -> anyhow::Result<MessageSignature> {
+        self.check_address(address)?;
+        if address.address_type() != Some(bitcoin::AddressType::P2pkh) {
+            bail!("Only p2pkh addresses can sign messages");
+        }
+        let private = self.get_private(wallet, &address.to_string())?;
+
+        let secp = Secp256k1::new();
+        let msg = bitcoin::secp256k1::Message::from(bellscoin_signed_msg_hash(message));
+        let signature = MessageSignature::new(secp.sign_ecdsa_recoverable(&msg, &private), true);
+
+        self.audit(wallet, AuditAction::SignMessage, None, format!("Signed message with {address}"));
+        Ok(signature)
+    }
+}
+
+/// Check that `signature` of `message` was made by key of p2pkh `address`
+pub fn verify_message(address: &bitcoin::Address, signature: &MessageSignature, message: &str) -> anyhow::Result<bool> {
+    let secp = Secp256k1::verification_only();
+    signature.is_signed_by_address(&secp, address, bellscoin_signed_msg_hash(message)).context("Failed to verify signature")
+}
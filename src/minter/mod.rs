@@ -1,59 +1,83 @@
-use std::{sync::Arc, fmt::Display, io::{Write, Read}, str::{from_utf8, FromStr}};
+use std::{collections::HashMap, sync::Arc, io::Read, path::{Path, PathBuf}, str::{from_utf8, FromStr}};
 
 use anyhow::{bail, Context};
 use itertools::Itertools;
-use tracing::{warn, error};
+use tracing::error;
 
-use crate::{data::{db::Database, MinterDbTables}, wallet::{Wallet, WalletAddressData}};
+use crate::{chain::Chain, data::{db::Database, MinterDbTables}, encryption::WalletKey, wallet::WalletAddressData};
 
+use self::{audit::{AuditAction, Redaction}, backend::ChainBackend};
+
+pub mod audit;
+pub mod backend;
+pub mod bump;
+pub mod coin_select;
+pub mod derivation;
+pub mod fetch;
+pub mod frozen;
+pub mod history;
+pub mod restore;
 pub mod utxo;
 pub mod wallet;
 pub mod inscribe;
+pub mod message;
+pub mod psbt;
+pub mod tx;
+#[cfg(test)]
+pub(crate) mod testing;
 
 pub struct Minter {
     pub db: Arc<Database>,
-    pub reqwest_client: reqwest::Client,
-    pub api_url: String,
+    /// Source of utxo's and address stats, relay of transactions
+    pub backend: Arc<dyn ChainBackend>,
+    /// Network of created wallets. Wallets of other networks are refused
+    pub network: Chain,
     pub tables: MinterDbTables,
+    /// Keys of unlocked encrypted wallets
+    keys: parking_lot::Mutex<HashMap<String, Arc<WalletKey>>>,
+    /// What is kept out of audit log and secrets file
+    redaction: Redaction,
+    /// File to append mnemonics and private keys to. Secrets are not backed up if None
+    secrets_backup: Option<PathBuf>,
 }
 
 impl Minter {
-    pub fn new(db_path: &str, api_url: String) -> anyhow::Result<Arc<Self>> {
+    /// Open DB at `db_path`, running its pending migrations if `migrate`. Secrets are appended to `secrets_file`
+    /// only with `Redaction::None`
+    pub fn new(db_path: &Path, backend: Arc<dyn ChainBackend>, network: Chain, redaction: Redaction, secrets_file: PathBuf, migrate: bool) -> anyhow::Result<Arc<Self>> {
         let db = Database::open(db_path)?;
+        if migrate {
+            db.migrate(false).context("Failed to migrate DB")?;
+        }
         let tables = MinterDbTables::load(&db).context("Failed to load column families from DB")?;
-        let reqwest_client = reqwest::Client::builder().user_agent("rust").build().context("Failed to build reqwest client")?;
 
         let minter = Arc::new(Self {
             db,
-            reqwest_client,
-            api_url,
+            backend,
+            network,
             tables,
+            keys: Default::default(),
+            redaction,
+            secrets_backup: (redaction == Redaction::None).then_some(secrets_file),
         });
 
         Ok(minter)
     }
 
-    pub fn push_important(&self, txt: impl Display) {
-        let file = std::fs::OpenOptions::new()
-            .append(true)
-            .create(true)
-            .open("important.log");
-        let Ok(mut file) = file else {
-            warn!("Failed to save important info: {txt}");
-            return;
-        };
-        if file.write_all(format!("[{}] {txt}\n", chrono::Utc::now().to_rfc2822()).as_bytes()).is_err() {
-            warn!("Failed to save important info: {txt}");
-        }
-    }
-
     pub fn push_address(&self, pub_key: &str, data: &WalletAddressData, wallet: &str) -> anyhow::Result<()> {
+        if data.has_private() && self.get_wallet(wallet)?.is_some_and(|x| x.is_watch_only()) {
+            bail!("Wallet #{wallet} is watch-only. Private keys can't be added to it");
+        }
         let mut key_start = wallet.to_owned();
         key_start.push('/');
         key_start.push_str(pub_key);
 
-        self.db.set(self.tables.addresses.table(), key_start.as_bytes(), data).context("Failed to save address")?;
-        self.push_important(format!("Created new address for #{wallet} {:?} with private: '{:?}' and public: '{}'", &data.ty, &data.private, &pub_key));
+        let protected = self.protect_address(wallet, pub_key, data.clone())?;
+        self.db.set(&self.tables.addresses.table()?, key_start.as_bytes(), &protected).context("Failed to save address")?;
+        if let Some(private) = &data.private {
+            self.backup_secret(wallet, "private", Some(pub_key), &private.display_secret().to_string());
+        }
+        self.audit(wallet, AuditAction::AddAddress, None, format!("Added {:?} address {pub_key}", data.ty));
         Ok(())
     }
 
@@ -62,8 +86,8 @@ impl Minter {
         key_start.push('/');
         key_start.push_str(pub_key);
 
-        self.db.remove(self.tables.addresses.table(), key_start.as_bytes()).context("Failed to remove address")?;
-        self.push_important(format!("Removed address '{pub_key}' in #{wallet}"));
+        self.db.remove(&self.tables.addresses.table()?, key_start.as_bytes()).context("Failed to remove address")?;
+        self.audit(wallet, AuditAction::RemoveAddress, None, format!("Removed address {pub_key}"));
         Ok(())
     }
 
@@ -72,7 +96,7 @@ impl Minter {
         key_start.push('/');
 
         let iter = self.db
-            .iterate(self.tables.addresses.table(), key_start.clone().into_bytes())
+            .iterate(&self.tables.addresses.table()?, key_start.clone().into_bytes())
             .context("Failed to query wallet addresses")?
             .filter_map(move |(key,val)| {
                 let Ok(addr) = from_utf8(&key) else {
@@ -96,7 +120,10 @@ impl Minter {
         key.push('/');
         key.push_str(pub_key);
 
-        self.db.get(self.tables.addresses.table(), key.as_bytes()).context("Failed to get address")
+        self.db.get_raw(&self.tables.addresses.table()?, key.as_bytes())
+            .context("Failed to get address")?
+            .map(|x| bincode::deserialize::<WalletAddressData>(&x).context("Invalid wallet address format"))
+            .transpose()
     }
 }
 
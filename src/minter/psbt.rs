@@ -0,0 +1,119 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use anyhow::{bail, Context};
+use bitcoin::{blockdata::script, psbt::Psbt, secp256k1::Secp256k1, EcdsaSighashType};
+
+use super::{tx::sign_input, utxo::UtxoData, Minter};
+
+impl Minter {
+    /// Unsigned PSBT of `tx` with `non_witness_utxo` of every input got from chain backend.
+    /// `prevouts[i]` is (address, utxo) spent by `tx.input[i]`
+    pub async fn create_psbt(&self, tx: bitcoin::Transaction, prevouts: &[(String, UtxoData)]) -> anyhow::Result<Psbt> {
+        if tx.input.len() != prevouts.len() {
+            bail!("Transaction has {} inputs, but {} previous outputs were given", tx.input.len(), prevouts.len());
+        }
+        let mut psbt = Psbt::from_unsigned_tx(tx).context("Failed to create PSBT")?;
+
+        let mut prev_txs = HashMap::new();
+        for (i, (_, utxo)) in prevouts.iter().enumerate() {
+            let prev_tx = match prev_txs.entry(utxo.txid) {
+                Entry::Occupied(x) => x.into_mut(),
+                Entry::Vacant(x) => x.insert(self.backend.transaction(&utxo.txid).await
+                    .with_context(|| format!("Failed to get transaction {}", utxo.txid))?),
+            };
+            if prev_tx.output.get(utxo.vout as usize).map(|x| x.value) != Some(utxo.value) {
+                bail!("Transaction {} doesn't match cached utxo {}:{}", utxo.txid, utxo.txid, utxo.vout);
+            }
+            psbt.inputs[i].non_witness_utxo = Some(prev_tx.clone());
+        }
+        Ok(psbt)
+    }
+
+    /// Add signatures of wallet keys to p2pkh inputs of `psbt`. Inputs of other wallets and watch-only addresses
+    /// are skipped. Returns number of signed inputs
+    pub fn sign_psbt(&self, wallet: &str, psbt: &mut Psbt) -> anyhow::Result<usize> {
+        self.check_can_sign(wallet)?;
+        let secp = Secp256k1::new();
+        let network = self.network.network();
+        let mut signed = 0;
+
+        for i in 0..psbt.inputs.len() {
+            let input = &psbt.inputs[i];
+            if input.final_script_sig.is_some() { continue; }
+            let prevout = psbt_prevout(psbt, i)?;
+            let Ok(address) = bitcoin::Address::from_script(&prevout.script_pubkey, network) else { continue };
+            let address = address.to_string();
+            let Some(addr_data) = self.get_address(wallet, &address)? else { continue };
+            let Some(private) = self.address_private(wallet, &address, &addr_data)? else {
+                debug!("Input {i} of watch-only address {address} is not signed");
+                continue;
+            };
+
+            if input.ecdsa_hash_ty().ok() != Some(EcdsaSighashType::All) {
+                bail!("Input {i} requests sighash type other than ALL");
+            }
+            let public = bitcoin::PublicKey::new(private.public_key(&secp));
+            if prevout.script_pubkey != bitcoin::Script::new_p2pkh(&public.pubkey_hash()) {
+                bail!("Input {i} of {address} is not p2pkh of compressed key");
+            }
+
+            let sig = sign_input(&secp, &psbt.unsigned_tx, i, &prevout.script_pubkey, &private)?;
+            psbt.inputs[i].partial_sigs.insert(public, sig);
+            signed += 1;
+        }
+        Ok(signed)
+    }
+}
+
+/// Output spent by input `index` of `psbt`, taken from its `non_witness_utxo`
+fn psbt_prevout(psbt: &Psbt, index: usize) -> anyhow::Result<bitcoin::TxOut> {
+    let outpoint = psbt.unsigned_tx.input[index].previous_output;
+    let prev_tx = psbt.inputs[index].non_witness_utxo.as_ref().with_context(|| format!("Input {index} has no non_witness_utxo"))?;
+    // otherwise signer could be fooled about value it spends
+    if prev_tx.txid() != outpoint.txid {
+        bail!("non_witness_utxo of input {index} is not transaction {}", outpoint.txid);
+    }
+    prev_tx.output.get(outpoint.vout as usize).cloned().with_context(|| format!("non_witness_utxo of input {index} has no output {}", outpoint.vout))
+}
+
+/// Fee paid by `psbt`, if all its inputs have `non_witness_utxo`
+pub fn psbt_fee(psbt: &Psbt) -> anyhow::Result<u64> {
+    let mut input = 0;
+    for i in 0..psbt.inputs.len() {
+        input += psbt_prevout(psbt, i)?.value;
+    }
+    let output = psbt.unsigned_tx.output.iter().map(|x| x.value).sum::<u64>();
+    input.checked_sub(output).context("PSBT outputs are more than inputs")
+}
+
+/// Build `final_script_sig` of every p2pkh input from its partial signature, as BIP 174 finalizer
+pub fn finalize_psbt(psbt: &mut Psbt) -> anyhow::Result<()> {
+    for i in 0..psbt.inputs.len() {
+        if psbt.inputs[i].final_script_sig.is_some() { continue; }
+        let prevout = psbt_prevout(psbt, i)?;
+
+        let input = &mut psbt.inputs[i];
+        let (public, sig) = input.partial_sigs.iter()
+            .find(|(public, _)| prevout.script_pubkey == bitcoin::Script::new_p2pkh(&public.pubkey_hash()))
+            .with_context(|| format!("Input {i} is not signed"))?;
+        input.final_script_sig = Some(script::Builder::new()
+            .push_slice(&sig.to_vec())
+            .push_key(public)
+            .into_script());
+
+        input.partial_sigs.clear();
+        input.sighash_type = None;
+        input.redeem_script = None;
+        input.witness_script = None;
+        input.bip32_derivation.clear();
+    }
+    Ok(())
+}
+
+/// Signed transaction of finalized `psbt`
+pub fn extract_psbt_tx(psbt: Psbt) -> anyhow::Result<bitcoin::Transaction> {
+    if let Some(i) = psbt.inputs.iter().position(|x| x.final_script_sig.is_none()) {
+        bail!("Input {i} of PSBT is not finalized");
+    }
+    Ok(psbt.extract_tx())
+}
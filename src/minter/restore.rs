@@ -0,0 +1,50 @@
+use anyhow::Context;
+
+use crate::wallet::{AddressType, WalletAddressData};
+
+use super::Minter;
+
+/// Number of consecutive unused addresses after which chain scanning stops
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RestoredAddress {
+    pub address: String,
+    #[serde(rename="type")] pub ty: AddressType,
+    pub index: u32,
+}
+
+impl Minter {
+    /// Scan utxo and ord derivation chains of wallet and save every address with transactions to DB.
+    /// Chain scanning stops after `gap_limit` consecutive unused addresses. Encrypted wallet must be unlocked
+    pub async fn restore_addresses(&self, wallet: &str, gap_limit: u32) -> anyhow::Result<Vec<RestoredAddress>> {
+        let wallet_data = self.get_wallet(wallet)?.context("Wallet not found")?;
+
+        let mut restored = vec![];
+        for ty in [AddressType::Utxo, AddressType::Ord] {
+            let mut gap = 0;
+            let mut index = 0;
+            while gap < gap_limit {
+                let derived = self.derive_address(&wallet_data, ty, index)?;
+                let address = derived.address.to_string();
+
+                let stats = self.get_address_from_api(&address).await.with_context(|| format!("Failed to check address {address}"))?;
+                if stats.is_used() {
+                    gap = 0;
+                    if self.get_address(wallet, &address)?.is_none() {
+                        self.push_address(&address, &WalletAddressData::derived(derived.private, ty, derived.path), wallet)?;
+                    }
+                    self.advance_index(wallet, ty, index)?;
+                    debug!("Found used {ty:?} address {address} at index {index}");
+                    restored.push(RestoredAddress { address, ty, index });
+                } else {
+                    gap += 1;
+                }
+                index += 1;
+            }
+            info!("Scanned {index} {ty:?} addresses of #{wallet}");
+        }
+
+        Ok(restored)
+    }
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use bitcoin::hashes::Hash;
+use tempfile::TempDir;
+
+use crate::{chain::Chain, wallet::AddressType};
+
+use super::{audit::Redaction, backend::{MockBackend, Utxo}, utxo::Status, Minter};
+
+/// Wallet created by `TestMinter::new`
+pub const WALLET: &str = "test";
+
+/// Minter on regtest with wallet `WALLET`, using mock backend and DB in temporary directory
+pub struct TestMinter {
+    pub minter: Arc<Minter>,
+    pub backend: Arc<MockBackend>,
+    _dir: TempDir,
+}
+
+impl TestMinter {
+    pub fn new() -> Self {
+        let dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let backend = Arc::new(MockBackend::new());
+        let minter = Minter::new(&dir.path().join("db"), backend.clone(), Chain::Regtest, Redaction::Secrets, dir.path().join("secrets.jsonl"), true).expect("Failed to open minter");
+        minter.create_wallet(String::new(), WALLET.to_owned()).expect("Failed to create wallet");
+        Self { minter, backend, _dir: dir }
+    }
+
+    /// Derive new address of `ty` and give it utxo of `value`
+    pub fn fund(&self, ty: AddressType, value: u64, confirmed: bool) -> (bitcoin::Address, bitcoin::OutPoint) {
+        let address = self.minter.receive_address(WALLET, ty).expect("Failed to derive address");
+        let outpoint = self.fund_address(&address, value, confirmed);
+        (address, outpoint)
+    }
+
+    /// Give `address` utxo of `value`, which is the only output of mocked transaction
+    pub fn fund_address(&self, address: &bitcoin::Address, value: u64, confirmed: bool) -> bitcoin::OutPoint {
+        let mut state = self.backend.state();
+        let tx = bitcoin::Transaction {
+            version: 1,
+            lock_time: bitcoin::PackedLockTime::ZERO,
+            // spent output differs for every transaction, so txid does too
+            input: vec![bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint { txid: bitcoin::Txid::all_zeros(), vout: state.transactions.len() as u32 },
+                script_sig: bitcoin::Script::new(),
+                sequence: bitcoin::Sequence::MAX,
+                witness: bitcoin::Witness::new(),
+            }],
+            output: vec![bitcoin::TxOut { value, script_pubkey: address.script_pubkey() }],
+        };
+        let outpoint = bitcoin::OutPoint { txid: tx.txid(), vout: 0 };
+        state.tx_status.insert(outpoint.txid, status(confirmed));
+        state.transactions.insert(outpoint.txid, tx);
+        state.utxo.entry(address.to_string()).or_default().push(Utxo {
+            txid: outpoint.txid,
+            vout: outpoint.vout,
+            status: status(confirmed),
+            value,
+            inscription_meta: None,
+            owner: None,
+        });
+        outpoint
+    }
+}
+
+pub fn status(confirmed: bool) -> Status {
+    Status { confirmed, block_height: confirmed.then_some(1), block_hash: None, block_time: None }
+}
+
+/// Script of address outside of wallet
+pub fn foreign_script() -> bitcoin::Script {
+    bitcoin::Script::new_p2pkh(&bitcoin::PubkeyHash::all_zeros())
+}
+
+/// Regtest address outside of wallet
+pub fn foreign_address() -> bitcoin::Address {
+    bitcoin::Address::from_script(&foreign_script(), bitcoin::Network::Regtest).expect("p2pkh script has address")
+}
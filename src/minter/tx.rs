@@ -0,0 +1,66 @@
+use anyhow::{bail, Context};
+use bitcoin::{blockdata::script, secp256k1::{Message, Secp256k1, SecretKey, Signing}, EcdsaSighashType};
+
+use super::{utxo::UtxoData, Minter};
+
+/// Outputs below this value are considered dust and are not created
+pub const DUST_LIMIT: u64 = 10_000;
+
+// sizes of legacy (non-segwit) transaction parts with compressed keys
+pub const TX_OVERHEAD_VSIZE: usize = 10;
+pub const P2PKH_INPUT_VSIZE: usize = 148;
+pub const P2PKH_OUTPUT_VSIZE: usize = 34;
+
+/// Estimate size of a transaction spending `inputs` p2pkh inputs to `outputs` p2pkh outputs
+pub fn estimate_p2pkh_vsize(inputs: usize, outputs: usize) -> usize {
+    TX_OVERHEAD_VSIZE + inputs * P2PKH_INPUT_VSIZE + outputs * P2PKH_OUTPUT_VSIZE
+}
+
+/// Sign `tx.input[index]` spending p2pkh output of compressed public key of `private`
+pub fn sign_p2pkh_input<C: Signing>(secp: &Secp256k1<C>, tx: &mut bitcoin::Transaction, index: usize, private: &SecretKey) -> anyhow::Result<()> {
+    let public = bitcoin::PublicKey::new(private.public_key(secp));
+    let script_pubkey = bitcoin::Script::new_p2pkh(&public.pubkey_hash());
+
+    let sig = sign_input(secp, tx, index, &script_pubkey, private)?;
+    tx.input[index].script_sig = script::Builder::new()
+        .push_slice(&sig.to_vec())
+        .push_key(&public)
+        .into_script();
+    Ok(())
+}
+
+/// Make SIGHASH_ALL signature of `tx.input[index]` using `script_code`
+pub fn sign_input<C: Signing>(secp: &Secp256k1<C>, tx: &bitcoin::Transaction, index: usize, script_code: &bitcoin::Script, private: &SecretKey) -> anyhow::Result<bitcoin::EcdsaSig> {
+    let sighash = tx.signature_hash(index, script_code, EcdsaSighashType::All.to_u32());
+    let msg = Message::from_slice(&sighash[..]).context("Invalid sighash")?;
+    Ok(bitcoin::EcdsaSig::sighash_all(secp.sign_ecdsa(&msg, private)))
+}
+
+impl Minter {
+    /// Sign p2pkh inputs of `tx`. `prevouts[i]` is (address, utxo) spent by `tx.input[i]`
+    pub fn sign_p2pkh_inputs(&self, wallet: &str, tx: &mut bitcoin::Transaction, prevouts: &[(String, UtxoData)]) -> anyhow::Result<()> {
+        if tx.input.len() != prevouts.len() {
+            bail!("Transaction has {} inputs, but {} previous outputs were given", tx.input.len(), prevouts.len());
+        }
+        let secp = Secp256k1::new();
+
+        for (i, (addr, _)) in prevouts.iter().enumerate() {
+            let private = self.get_private(wallet, addr)?;
+            sign_p2pkh_input(&secp, tx, i, &private)?;
+        }
+        Ok(())
+    }
+
+    /// Get private key of wallet address or fail if address can't sign
+    pub fn get_private(&self, wallet: &str, addr: &str) -> anyhow::Result<SecretKey> {
+        self.check_can_sign(wallet)?;
+        let addr_data = self.get_address(wallet, addr)?.with_context(|| format!("Address {addr} not found in wallet"))?;
+        self.address_private(wallet, addr, &addr_data)?.with_context(|| format!("No private key for address {addr}"))
+    }
+
+    /// Broadcast signed transaction using chain backend
+    pub async fn broadcast_tx(&self, tx: &bitcoin::Transaction) -> anyhow::Result<bitcoin::Txid> {
+        debug!("Broadcasting tx {}", tx.txid());
+        self.backend.broadcast(tx).await
+    }
+}
@@ -1,12 +1,13 @@
-use std::{collections::HashMap, str::{from_utf8, FromStr}};
+use std::{collections::{HashMap, HashSet}, str::{from_utf8, FromStr}};
 
-use anyhow::{bail, Context};
+use anyhow::Context;
 use bitcoin::BlockHash;
 use itertools::Itertools;
 
-use crate::wallet::{AddressType, WalletAddressData};
+use crate::{wallet::{AddressType, WalletAddressData}, FeeRate};
+
+use super::{audit::AuditAction, backend::{AddressStats, Utxo}, coin_select::{select_coins, CoinSelectionParams, Selection}, tx::estimate_p2pkh_vsize};
 
-// bincode does not support 'flatten' but we need it to access api
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct UtxoData {
     pub txid: bitcoin::Txid,
@@ -17,6 +18,20 @@ pub struct UtxoData {
     #[serde(default)] pub inscription_meta: Option<InscriptionMeta>,
     #[serde(default)] pub owner: Option<String>,
 }
+impl UtxoData {
+    pub fn new(utxo: Utxo, ty: AddressType) -> Self {
+        Self {
+            txid: utxo.txid,
+            vout: utxo.vout,
+            status: utxo.status,
+            value: utxo.value,
+            ty,
+            inscription_meta: utxo.inscription_meta,
+            owner: utxo.owner,
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct InscriptionMeta {
     pub content_type: String,
@@ -27,16 +42,6 @@ pub struct InscriptionMeta {
     pub number: usize,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct UtxoApiData {
-    pub txid: String,
-    pub vout: u32,
-    pub status: Status,
-    pub value: u64,
-    #[serde(default, flatten)] pub inscription_meta: Option<InscriptionApiMeta>,
-    #[serde(default)] pub owner: Option<String>,
-}
-
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Status {
     pub confirmed: bool,
@@ -51,16 +56,6 @@ pub struct InscriptionId {
     pub index: u32,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-pub struct InscriptionApiMeta {
-    pub content_type: String,
-    pub content_length: usize,
-    pub outpoint: bitcoin::Txid,
-    pub genesis: bitcoin::Txid,
-    pub inscription_id: InscriptionId,
-    pub number: usize,
-}
-
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct UtxoList {
     pub addr: String,
@@ -107,7 +102,7 @@ impl<'a> Iterator for UtxoMultiListIterator<'a> {
             let list = self.utxo.per_address.get(self.offset_a)?;
             if let Some(utxo) = list.utxo.get(self.offset_b) {
                 self.offset_b += 1;
-                return Some((&list.addr, &utxo))
+                return Some((&list.addr, utxo))
             }
             self.offset_b = 0;
             self.offset_a += 1;
@@ -129,7 +124,7 @@ impl<'a> UtxoMultiListIterator<'a> {
 impl UtxoMultiList {
     pub fn new() -> Self { UtxoMultiList { per_address: vec![] }}
     pub fn with_capacity(cap: usize) -> Self { UtxoMultiList { per_address: Vec::with_capacity(cap) }}
-    pub fn iter(&self) -> UtxoMultiListIterator { UtxoMultiListIterator { utxo: self, offset_a: 0, offset_b: 0 } }
+    pub fn iter(&self) -> UtxoMultiListIterator<'_> { UtxoMultiListIterator { utxo: self, offset_a: 0, offset_b: 0 } }
     pub fn push(&mut self, l: UtxoList) { self.per_address.push(l); }
 
     pub fn len(&self) -> usize { self.per_address.iter().map(|x|x.utxo.len()).sum() }
@@ -137,6 +132,20 @@ impl UtxoMultiList {
 }
 impl Default for UtxoMultiList { fn default() -> Self { Self::new() } }
 
+/// Changes of cached utxo's made by `Minter::fetch_utxo`. Items are (address, utxo)
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct UtxoDiff {
+    /// Utxo's which were not cached
+    pub added: Vec<(String, UtxoData)>,
+    /// Cached utxo's no longer reported by backend
+    pub spent: Vec<(String, UtxoData)>,
+    /// Cached utxo's which got confirmed or went back to mempool, with their new status
+    pub status_changed: Vec<(String, UtxoData)>,
+}
+impl UtxoDiff {
+    pub fn is_empty(&self) -> bool { self.added.is_empty() && self.spent.is_empty() && self.status_changed.is_empty() }
+}
+
 
 
 impl super::Minter {
@@ -146,7 +155,7 @@ impl super::Minter {
         prefix.push('/');
         prefix.push_str(address);
 
-        let utxo = self.db.iterate(self.tables.utxo.table(), prefix.into_bytes())
+        let utxo = self.db.iterate(&self.tables.utxo.table()?, prefix.into_bytes())
             .context("Failed to get utxo's")?
             .filter_map(|(_,v)| {
                 let Ok(data) = bincode::deserialize::<UtxoData>(&v) else {
@@ -161,7 +170,7 @@ impl super::Minter {
 
     //todo: better error log
     fn get_all_utxo_for(&self, addresses: &mut HashMap<String,UtxoList>, wallet: &str) -> anyhow::Result<()> {
-        let iter = self.db.iterate(self.tables.utxo.table(), wallet.to_owned().into_bytes())
+        let iter = self.db.iterate(&self.tables.utxo.table()?, wallet.to_owned().into_bytes())
             .context("Failed to get utxo's")?;
         for (k,v) in iter {
             let Some(addr) = k.split(|&x|x==b'/').nth(1) else {
@@ -191,133 +200,367 @@ impl super::Minter {
         self.get_all_utxo_for(&mut addresses, wallet).map(|_|addresses.into())
     }
 
-    //todo: add timeouts
-    /// Get utxo's from api without any DB interaction
-    async fn get_utxo_from_api(&self, address: &str, ty: AddressType) -> anyhow::Result<Vec<UtxoData>> {
-        debug!("Retrieving utxo of address {}", address);
-    
-        let url = format!("{}/address/{}/utxo", &self.api_url.trim_end_matches('/'), &address);
-        let resp = self.reqwest_client.get(url).send().await.context("Failed to send api get utxo request")?;
-        
-        match resp.status() {
-            reqwest::StatusCode::OK => Ok(
-                resp.json::<Vec<UtxoApiData>>()
-                    .await
-                    .context("Api get utxo invalid json")?
-                    .into_iter()
-                    .map(|x| UtxoData {
-                        txid: bitcoin::Txid::from_str(&x.txid).unwrap(), //todo: remove unwrap
-                        vout: x.vout,
-                        status: x.status,
-                        value: x.value,
-                        inscription_meta: x.inscription_meta.map(|x| InscriptionMeta { 
-                            content_type: x.content_type, 
-                            content_length: x.content_length, 
-                            outpoint: bitcoin::OutPoint { txid: x.outpoint, vout: 0 }, 
-                            genesis: bitcoin::OutPoint { txid: x.genesis, vout: 0 }, 
-                            inscription_id: x.inscription_id, 
-                            number: x.number,
-                        }),
-                        owner: x.owner,
-                        ty,
-                    })
-                    .collect_vec()
-            ),
-            err => bail!("Api get utxo error: {err}")
-        }
+    /// Get transaction stats of address from chain backend
+    pub async fn get_address_from_api(&self, address: &str) -> anyhow::Result<AddressStats> {
+        debug!("Retrieving stats of address {}", address);
+        self.backend.address_stats(address).await
     }
 
-    /// Get utxo's from api without any DB interaction
+    /// Get utxo's from chain backend without any DB interaction
     async fn get_all_utxo_from_api(&self, wallet: &str, selector: impl Fn(&str, &WalletAddressData) -> bool) -> anyhow::Result<UtxoMultiList> {
-        let mut utxo = UtxoMultiList::new();
-        for (addr, addr_data) in self.addresses(wallet)? {
-            if !selector(&addr, &addr_data) { continue; }
-            let new_utxo = self.get_utxo_from_api(&addr, addr_data.ty).await.context("Failed to get utxo")?;
-            utxo.push(UtxoList {
-                addr,
-                utxo: new_utxo,
-            });
-        }
-        Ok(utxo)
+        let addresses = self.addresses(wallet)?
+            .filter(|(addr, addr_data)| selector(addr, addr_data))
+            .collect_vec();
+
+        let fetched = self.fetch_addresses("utxo", addresses.iter().map(|x| x.0.clone()).collect(), |backend, addr| Box::pin(async move {
+            backend.utxo(&addr).await
+        })).await?;
+
+        Ok(fetched.into_iter()
+            .zip(addresses)
+            .map(|((addr, utxo), (_, addr_data))| UtxoList::new(addr, utxo.into_iter().map(|x| UtxoData::new(x, addr_data.ty)).collect()))
+            .collect_vec()
+            .into())
     }
 
     /// Remove all saved utxo's from DB
     pub fn clear_saved_utxo(&self, wallet: &str, selector: impl Fn(&str, &UtxoData) -> bool) -> anyhow::Result<usize> {
         let mut prefix = wallet.to_owned().into_bytes();
         prefix.push(b'/');
-        self.db.remove_where(self.tables.utxo.table(), prefix, |k,v| {
+        self.db.remove_where(&self.tables.utxo.table()?, prefix, |k,v| {
             let Some(k) = k else { return true };
             let Some(v) = v else { return true };
             selector(k,&v)
         }).context("Failed to delete saved utxo")
     }
 
-    //todo: remove and set in one transaction
     //todo: better path for DB keys
     //todo: better selectors?
-    //todo: implement more clever way to check updates
-    //todo: implement more clever way to overwrite values (so only changed items will be updated)
-    pub async fn fetch_utxo(&self, wallet: &str, wallet_selector: impl Fn(&str, &WalletAddressData) -> bool, utxo_selector: impl Fn(&str, &UtxoData) -> bool) -> anyhow::Result<UtxoMultiList> {
+    /// Refresh cached utxo's of addresses matching `wallet_selector` from chain backend. Cached utxo's matching
+    /// `utxo_selector` which backend no longer reports are removed as spent. Only changed rows are written,
+    /// all in one batch, so failed refresh leaves cache as it was
+    pub async fn fetch_utxo(&self, wallet: &str, wallet_selector: impl Fn(&str, &WalletAddressData) -> bool, utxo_selector: impl Fn(&str, &UtxoData) -> bool) -> anyhow::Result<(UtxoMultiList, UtxoDiff)> {
         debug!("Fetching utxo's");
         let utxo = self.get_all_utxo_from_api(wallet, &wallet_selector).await?;
+        let mut fetched = utxo.iter()
+            .map(|(addr,x)| (utxo_key(wallet, addr, x), (addr, x)))
+            .collect::<HashMap<_,_>>();
 
-        //todo: drop utxo on error
-        let removed = self.clear_saved_utxo(wallet, &utxo_selector)?;
-        debug!("Removed {removed} utxo for {wallet}");
-
-        //todo: optimize
-        self.db.set_many(self.tables.utxo.table(), utxo.iter().map(|(addr,x)| {
-                let mut key = wallet.to_owned();
-                key.push('/');
-                key.push_str(addr);
-                key.push('/');
-                key.push_str(&x.txid.to_string());
-                key.push(':');
-                key.push_str(&x.vout.to_string());
-                (key.into_bytes(), x)
+        let mut prefix = wallet.to_owned().into_bytes();
+        prefix.push(b'/');
+        let mut diff = UtxoDiff::default();
+        let mut put = vec![];
+        let mut delete = vec![];
+        for (k,v) in self.db.iterate(&self.tables.utxo.table()?, prefix).context("Failed to get saved utxo")? {
+            let key = from_utf8(&k).ok();
+            let cached = key.and_then(|_| bincode::deserialize::<UtxoData>(&v).ok());
+            let (Some(key), Some(cached)) = (key, cached) else {
+                error!("Invalid UTXO row. Removing it");
+                delete.push(k.into_vec());
+                continue;
+            };
+            match fetched.remove(k.as_ref()) {
+                Some((addr, x)) => {
+                    if cached.status.confirmed != x.status.confirmed {
+                        diff.status_changed.push((addr.to_owned(), x.clone()));
+                    }
+                    if bincode::serialize(x).context("Failed to serialize utxo")? != *v {
+                        put.push((k.into_vec(), x));
+                    }
+                }
+                None if utxo_selector(key, &cached) => {
+                    let addr = key.split('/').nth(1).unwrap_or_default().to_owned();
+                    diff.spent.push((addr, cached));
+                    delete.push(k.into_vec());
+                }
+                None => {}
+            }
+        }
+        for (addr, x) in utxo.iter() {
+            let key = utxo_key(wallet, addr, x);
+            if fetched.contains_key(&key) {
+                diff.added.push((addr.to_owned(), x.clone()));
+                put.push((key, x));
             }
-        )).context("Failed to push new utxo's")?;
+        }
 
-        let added = utxo.len();
-        debug!("Added {added} utxo for {wallet}");
+        self.db.write_many(&self.tables.utxo.table()?, put, delete).context("Failed to update saved utxo")?;
+        debug!("Utxo of {wallet}: {} added, {} spent, {} changed status", diff.added.len(), diff.spent.len(), diff.status_changed.len());
 
-        Ok(utxo)
+        Ok((utxo, diff))
     }
 
-    //todo: update in DB after transactions
-    pub async fn gather_utxo(&self, wallet: &str, ty: AddressType, value: u64) -> anyhow::Result<Vec<(String, UtxoData)>> {
-        let mut cur_value = 0;
-        let mut gathered_utxo = vec![];
+    /// Select utxo's of type `ty` paying for `params`. Uses cached utxo's and refreshes them from api if they are not enough.
+    /// Utxo's carrying inscriptions and frozen outpoints are never selected
+    pub async fn gather_utxo(&self, wallet: &str, ty: AddressType, params: &CoinSelectionParams) -> anyhow::Result<Selection> {
+        let frozen = self.frozen_outpoints(wallet)?;
+        let candidates = |utxo: &UtxoMultiList| utxo.iter()
+            .filter(|(_,x)| Self::is_spendable(x, &frozen))
+            .map(|(addr,x)| (addr.to_owned(), x.clone()))
+            .collect_vec();
 
-        for (addr,utxo) in self.get_all_utxo(wallet, |_,v| v.ty == ty).context("Failed to get cached utxo")?.iter() {
-            cur_value += utxo.value;
-            gathered_utxo.push((addr.to_owned(), utxo.clone()));
-            if cur_value >= value { return Ok(gathered_utxo); }
+        let cached = self.get_all_utxo(wallet, |_,v| v.ty == ty).context("Failed to get cached utxo")?;
+        match select_coins(candidates(&cached), params) {
+            Ok(selection) => return Ok(selection),
+            Err(err) => info!("Not enough cached utxo ({err}). Getting new from api"),
         }
 
-        info!("Not enough cached utxo. Getting new from api");
-        cur_value = 0;
-        gathered_utxo.clear();
-        for (addr,utxo) in self.fetch_utxo(wallet, |_,v| v.ty == ty, |_,v| v.ty == ty).await.context("Failed to get cached utxo")?.iter() {
-            cur_value += utxo.value;
-            gathered_utxo.push((addr.to_owned(), utxo.clone()));
-            if cur_value >= value { return Ok(gathered_utxo); }
+        let (fetched, _) = self.fetch_utxo(wallet, |_,v| v.ty == ty, |_,v| v.ty == ty).await.context("Failed to fetch utxo")?;
+        Ok(select_coins(candidates(&fetched), params)?)
+    }
+
+    /// Remove spent utxo's from DB
+    pub fn remove_spent_utxo(&self, wallet: &str, spent: &[(String, UtxoData)]) -> anyhow::Result<()> {
+        for (addr, utxo) in spent {
+            self.db.remove(&self.tables.utxo.table()?, utxo_key(wallet, addr, utxo)).context("Failed to remove spent utxo")?;
+        }
+        Ok(())
+    }
+
+    /// Add utxo-type inputs to `tx` paying for its outputs and fee, and a change output if it is not dust.
+    /// `prevouts` are (address, utxo) spent by inputs already in `tx`, added inputs are appended to it
+    pub async fn fund_tx(&self, wallet: &str, tx: &mut bitcoin::Transaction, prevouts: &mut Vec<(String, UtxoData)>, fee_rate: FeeRate) -> anyhow::Result<()> {
+        let selection = self.gather_utxo(wallet, AddressType::Utxo, &CoinSelectionParams {
+            target: tx.output.iter().map(|x| x.value).sum(),
+            existing: prevouts.iter().map(|(_,x)| x.value).sum(),
+            base_vsize: estimate_p2pkh_vsize(prevouts.len(), tx.output.len()),
+            fee_rate,
+        }).await.context("Failed to retrieve available utxo's for transaction")?;
+        debug!("Selected {} utxo's, fee {}, change {}", selection.utxo.len(), selection.fee, selection.change);
+
+        tx.input.extend(selection.utxo.iter().map(|(_,x)| bitcoin::TxIn {
+            previous_output: bitcoin::OutPoint { txid: x.txid, vout: x.vout },
+            script_sig: bitcoin::Script::new(),
+            sequence: bitcoin::Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: bitcoin::Witness::new(),
+        }));
+
+        if selection.change > 0 {
+            // change goes back to the first spent utxo address, or to new one if inputs already in transaction are enough
+            let funding = selection.utxo.iter().chain(prevouts.iter()).find(|(_,x)| x.ty == AddressType::Utxo);
+            let change_address = match funding {
+                Some((addr, _)) => bitcoin::Address::from_str(addr).context("Invalid change address")?,
+                None => self.receive_address(wallet, AddressType::Utxo).context("Failed to derive change address")?,
+            };
+            tx.output.push(bitcoin::TxOut {
+                value: selection.change,
+                script_pubkey: change_address.script_pubkey(),
+            });
         }
 
-        warn!("Not enough utxo");
-        
-        Ok(vec![])
+        prevouts.extend(selection.utxo);
+        Ok(())
+    }
+
+    /// Sign and broadcast transaction spending wallet `prevouts`, then forget them in DB
+    async fn sign_and_broadcast(&self, wallet: &str, mut tx: bitcoin::Transaction, prevouts: &[(String, UtxoData)]) -> anyhow::Result<bitcoin::Txid> {
+        trace!("Signing transaction");
+        self.sign_p2pkh_inputs(wallet, &mut tx, prevouts).context("Failed to sign transaction")?;
+
+        let txid = self.broadcast_tx(&tx).await.context("Failed to broadcast transaction")?;
+        self.remove_spent_utxo(wallet, prevouts)?;
+        Ok(txid)
+    }
+
+    /// Build unsigned transaction sending `amount` to `dest`, funded with utxo addresses.
+    /// Returns it with (address, utxo) spent by its inputs
+    pub async fn build_send_utxo(&self, wallet: &str, dest: &bitcoin::Address, amount: bitcoin::Amount, fee_rate: FeeRate) -> anyhow::Result<(bitcoin::Transaction, Vec<(String, UtxoData)>)> {
+        self.check_address(dest)?;
+
+        let mut tx = bitcoin::Transaction {
+            version: 1,
+            lock_time: bitcoin::PackedLockTime::ZERO,
+            input: vec![],
+            output: vec![bitcoin::TxOut {
+                value: amount.to_sat(),
+                script_pubkey: dest.script_pubkey(),
+            }],
+        };
+        let mut prevouts = vec![];
+        self.fund_tx(wallet, &mut tx, &mut prevouts, fee_rate).await?;
+        Ok((tx, prevouts))
     }
 
-    pub async fn send_utxo(&self, wallet: &str, dest: bitcoin::Address, amount: bitcoin::Amount) -> anyhow::Result<()> {
+    pub async fn send_utxo(&self, wallet: &str, dest: bitcoin::Address, amount: bitcoin::Amount, fee_rate: FeeRate) -> anyhow::Result<bitcoin::Txid> {
         debug!("Sending tx");
+        let (tx, prevouts) = self.build_send_utxo(wallet, &dest, amount, fee_rate).await?;
 
-        trace!("Collecting utxo's for transaction");
-        let utxo = self.gather_utxo(wallet, AddressType::Utxo, amount.to_sat()).await.context("Failed to retrieve available utxo's for transaction")?;
-        
-        
+        let txid = self.sign_and_broadcast(wallet, tx, &prevouts).await?;
+        self.audit(wallet, AuditAction::Send, Some(txid), format!("Sent {amount} to {dest}"));
+        info!("Sent {amount} to {dest} in {txid}");
+        Ok(txid)
+    }
 
-        dbg!(utxo);
-        Ok(())
+    /// Find cached utxo holding inscription on ord address. Refreshes cache if not found
+    pub async fn find_inscription_utxo(&self, wallet: &str, inscription_id: &crate::InscriptionId) -> anyhow::Result<(String, UtxoData)> {
+        let is_ord = |_: &str, x: &WalletAddressData| x.ty == AddressType::Ord;
+        let matches = |x: &UtxoData| x.inscription_meta.as_ref()
+            .is_some_and(|m| m.inscription_id.txid == inscription_id.txid && m.inscription_id.index == inscription_id.index);
+
+        let cached = self.get_all_utxo(wallet, is_ord).context("Failed to get cached utxo")?;
+        if let Some((addr, utxo)) = cached.iter().find(|(_,x)| matches(x)) {
+            return Ok((addr.to_owned(), utxo.clone()));
+        }
+
+        info!("Inscription {inscription_id} not found in cache. Getting new utxo's from api");
+        let (fetched, _) = self.fetch_utxo(wallet, is_ord, |_,x| x.ty == AddressType::Ord).await.context("Failed to fetch utxo")?;
+        fetched.iter()
+            .find(|(_,x)| matches(x))
+            .map(|(addr, utxo)| (addr.to_owned(), utxo.clone()))
+            .with_context(|| format!("Inscription {inscription_id} not found on ord addresses of wallet {wallet}"))
+    }
+
+    /// Build unsigned transaction sending inscription to `dest`. Inscribed utxo is spent as input 0 and its whole value
+    /// goes to output 0, so the inscribed sat lands at `dest`. Fee is paid with utxo-type addresses.
+    /// Returns it with (address, utxo) spent by its inputs
+    pub async fn build_send_inscription(&self, wallet: &str, dest: &bitcoin::Address, inscription_id: &crate::InscriptionId, fee_rate: FeeRate) -> anyhow::Result<(bitcoin::Transaction, Vec<(String, UtxoData)>)> {
+        self.check_address(dest)?;
+
+        let (addr, utxo) = self.find_inscription_utxo(wallet, inscription_id).await?;
+
+        let mut tx = bitcoin::Transaction {
+            version: 1,
+            lock_time: bitcoin::PackedLockTime::ZERO,
+            input: vec![bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint { txid: utxo.txid, vout: utxo.vout },
+                script_sig: bitcoin::Script::new(),
+                sequence: bitcoin::Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: bitcoin::Witness::new(),
+            }],
+            output: vec![bitcoin::TxOut {
+                value: utxo.value,
+                script_pubkey: dest.script_pubkey(),
+            }],
+        };
+        let mut prevouts = vec![(addr, utxo)];
+        self.fund_tx(wallet, &mut tx, &mut prevouts, fee_rate).await?;
+        Ok((tx, prevouts))
+    }
+
+    /// Send inscription to `dest`
+    pub async fn send_inscription(&self, wallet: &str, dest: bitcoin::Address, inscription_id: &crate::InscriptionId, fee_rate: FeeRate) -> anyhow::Result<bitcoin::Txid> {
+        debug!("Sending inscription {inscription_id}");
+        let (tx, prevouts) = self.build_send_inscription(wallet, &dest, inscription_id, fee_rate).await?;
+
+        let txid = self.sign_and_broadcast(wallet, tx, &prevouts).await?;
+        self.audit(wallet, AuditAction::SendInscription, Some(txid), format!("Sent inscription {inscription_id} to {dest}"));
+        info!("Sent inscription {inscription_id} to {dest} in {txid}");
+        Ok(txid)
+    }
+
+    /// Remove cached utxo's of wallet spent by `tx`
+    pub fn remove_spent_by(&self, wallet: &str, tx: &bitcoin::Transaction) -> anyhow::Result<usize> {
+        let spent = tx.input.iter().map(|x| x.previous_output).collect::<HashSet<_>>();
+        self.clear_saved_utxo(wallet, |_,x| spent.contains(&bitcoin::OutPoint { txid: x.txid, vout: x.vout }))
+    }
+}
+
+fn utxo_key(wallet: &str, addr: &str, utxo: &UtxoData) -> Vec<u8> {
+    let mut key = wallet.to_owned();
+    key.push('/');
+    key.push_str(addr);
+    key.push('/');
+    key.push_str(&utxo.txid.to_string());
+    key.push(':');
+    key.push_str(&utxo.vout.to_string());
+    key.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{minter::{testing::{foreign_script, status, TestMinter, WALLET}, tx::estimate_p2pkh_vsize}, wallet::AddressType, FeeRate};
+
+    fn payment(value: u64) -> bitcoin::Transaction {
+        bitcoin::Transaction {
+            version: 1,
+            lock_time: bitcoin::PackedLockTime::ZERO,
+            input: vec![],
+            output: vec![bitcoin::TxOut { value, script_pubkey: foreign_script() }],
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_utxo_reports_diff_of_cache() {
+        let test = TestMinter::new();
+        let (address, first) = test.fund(AddressType::Utxo, 100_000, false);
+        let second = test.fund_address(&address, 200_000, true);
+
+        let (_, diff) = test.minter.fetch_utxo(WALLET, |_,_| true, |_,_| true).await.unwrap();
+        assert_eq!(diff.added.len(), 2);
+        assert!(diff.spent.is_empty() && diff.status_changed.is_empty());
+
+        {
+            let mut state = test.backend.state();
+            let utxo = state.utxo.get_mut(&address.to_string()).unwrap();
+            utxo.retain(|x| x.txid != second.txid);
+            utxo[0].status = status(true);
+        }
+        let (_, diff) = test.minter.fetch_utxo(WALLET, |_,_| true, |_,_| true).await.unwrap();
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.spent.iter().map(|(_, x)| x.txid).collect::<Vec<_>>(), vec![second.txid]);
+        assert_eq!(diff.status_changed.iter().map(|(_, x)| x.txid).collect::<Vec<_>>(), vec![first.txid]);
+
+        let cached = test.minter.get_utxo(&address.to_string(), WALLET).unwrap();
+        assert_eq!(cached.utxo.len(), 1);
+        assert!(cached.utxo[0].status.confirmed);
+
+        let (_, diff) = test.minter.fetch_utxo(WALLET, |_,_| true, |_,_| true).await.unwrap();
+        assert!(diff.is_empty());
+    }
+
+    #[tokio::test]
+    async fn fetch_utxo_keeps_utxo_not_matching_selector() {
+        let test = TestMinter::new();
+        let (address, _) = test.fund(AddressType::Utxo, 100_000, true);
+        test.minter.fetch_utxo(WALLET, |_,_| true, |_,_| true).await.unwrap();
+
+        test.backend.state().utxo.clear();
+        let (_, diff) = test.minter.fetch_utxo(WALLET, |_,_| true, |_,_| false).await.unwrap();
+        assert!(diff.spent.is_empty());
+        assert_eq!(test.minter.get_utxo(&address.to_string(), WALLET).unwrap().utxo.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn fund_tx_pays_fee_and_adds_change() {
+        let test = TestMinter::new();
+        let (address, outpoint) = test.fund(AddressType::Utxo, 1_000_000, true);
+        let mut tx = payment(300_000);
+        let mut prevouts = vec![];
+        test.minter.fund_tx(WALLET, &mut tx, &mut prevouts, FeeRate::try_from(2.0).unwrap()).await.unwrap();
+
+        assert_eq!(tx.input.iter().map(|x| x.previous_output).collect::<Vec<_>>(), vec![outpoint]);
+        assert_eq!(prevouts.len(), 1);
+        assert_eq!(tx.output.len(), 2);
+        assert_eq!(tx.output[1].script_pubkey, address.script_pubkey());
+        let fee = 1_000_000 - tx.output.iter().map(|x| x.value).sum::<u64>();
+        assert_eq!(fee, 2 * estimate_p2pkh_vsize(1, 2) as u64);
+    }
+
+    #[tokio::test]
+    async fn fund_tx_returns_surplus_of_existing_inputs_as_change() {
+        let test = TestMinter::new();
+        let (address, outpoint) = test.fund(AddressType::Utxo, 1_000_000, true);
+        let (utxo, _) = test.minter.fetch_utxo(WALLET, |_,_| true, |_,_| true).await.unwrap();
+        let mut prevouts = utxo.iter().map(|(addr, x)| (addr.to_owned(), x.clone())).collect::<Vec<_>>();
+        let mut tx = payment(300_000);
+        tx.input.push(bitcoin::TxIn { previous_output: outpoint, ..Default::default() });
+        test.minter.fund_tx(WALLET, &mut tx, &mut prevouts, FeeRate::try_from(1.0).unwrap()).await.unwrap();
+
+        assert_eq!(tx.input.len(), 1);
+        assert_eq!(tx.output.len(), 2);
+        assert_eq!(tx.output[1].script_pubkey, address.script_pubkey());
+        assert_eq!(tx.output[1].value, 1_000_000 - 300_000 - estimate_p2pkh_vsize(1, 2) as u64);
+    }
+
+    #[tokio::test]
+    async fn fund_tx_never_spends_ord_address() {
+        let test = TestMinter::new();
+        test.fund(AddressType::Ord, 1_000_000, true);
+        test.fund(AddressType::Utxo, 100_000, true);
+        let mut tx = payment(300_000);
+        let mut prevouts = vec![];
+        let err = test.minter.fund_tx(WALLET, &mut tx, &mut prevouts, FeeRate::try_from(1.0).unwrap()).await.unwrap_err();
+        assert!(format!("{err:#}").contains("insufficient funds"), "{err:#}");
+        assert!(tx.input.is_empty() && prevouts.is_empty());
     }
 }
@@ -2,18 +2,16 @@ use std::str::FromStr;
 
 use anyhow::{bail, Context};
 
-use crate::wallet::Wallet;
+use std::sync::Arc;
 
-use super::Minter;
+use bitcoin::{secp256k1::SecretKey, util::bip32::ExtendedPubKey};
 
+use crate::{data::db::Database, encryption::{KdfParams, WalletKey}, wallet::{Wallet, WalletAddressData, WalletSecrets}};
+
+use super::{audit::AuditAction, Minter};
 
-impl Minter {
-    pub fn push_wallet(&self, id: &str, wallet: &Wallet) -> anyhow::Result<()> {
-        self.db.set(self.tables.wallets.table(), id.as_bytes(), wallet)?;
-		self.push_important(format!("Created a new wallet with mnemonic: {}", &wallet.mnemonic));
-        Ok(())
-    }
 
+impl Minter {
     pub fn create_wallet(&self, passphrase: String, name: String) -> anyhow::Result<Wallet> {
         use bitcoin::secp256k1::rand::RngCore;
 
@@ -27,24 +25,63 @@ impl Minter {
         if self.get_wallet(&name)?.is_some() {
 			bail!("Wallet {} already exists. Create new one with --wallet <name> flag", &name);
 		}
-		let wallet = Wallet::new(mnemonic, Some(passphrase.clone()), name.clone());
-		let mnemonic = bip39::Mnemonic::from_str(&wallet.mnemonic).context("Invalid mnemonic")?;
+		let mnemonic = bip39::Mnemonic::from_str(&mnemonic).context("Invalid mnemonic")?;
+		let wallet = Wallet::new(mnemonic.to_string(), Some(passphrase.clone()), name.clone(), self.network);
 
-		self.db.set(self.tables.wallets.table(), &name, &wallet).context("Failed to save wallet to database")?;
+		self.db.set(&self.tables.wallets.table()?, &name, &wallet).context("Failed to save wallet to database")?;
 
-		self.push_important("There is no wallet saved in DB");
-		self.push_important(format!("Created new wallet with mnemonic: {mnemonic}"));
+		self.backup_secret(&name, "mnemonic", None, &mnemonic.to_string());
+		self.backup_secret(&name, "passphrase", None, &passphrase);
+		self.audit(&name, AuditAction::CreateWallet, None, "Created wallet");
+
+        Ok(wallet)
+    }
+
+    /// Create watch-only wallet deriving addresses from account `xpub`, or holding imported addresses if it is None
+    pub fn create_watch_only_wallet(&self, name: String, xpub: Option<ExtendedPubKey>) -> anyhow::Result<Wallet> {
+        if self.get_wallet(&name)?.is_some() {
+            bail!("Wallet {} already exists. Create new one with --wallet <name> flag", &name);
+        }
+        if let Some(xpub) = &xpub {
+            // bip32 keys only tell mainnet from test networks
+            if (xpub.network == bitcoin::Network::Bellscoin) != (self.network.network() == bitcoin::Network::Bellscoin) {
+                bail!("Xpub belongs to {}, but {} is used", xpub.network, self.network);
+            }
+            if xpub.depth != 3 {
+                bail!("Xpub must be of account m/44'/{}'/0' with depth 3, but has depth {}", self.network.coin_type(), xpub.depth);
+            }
+        }
+        let wallet = Wallet::watch_only(xpub, name.clone(), self.network);
+
+        self.db.set(&self.tables.wallets.table()?, &name, &wallet).context("Failed to save wallet to database")?;
+        self.audit(&name, AuditAction::CreateWallet, None, "Created watch-only wallet");
 
         Ok(wallet)
     }
 
     pub fn get_wallet(&self, id: &str) -> anyhow::Result<Option<Wallet>> {
-        self.db.get(self.tables.wallets.table(), id.as_bytes())
+        self.db.get_raw(&self.tables.wallets.table()?, id.as_bytes())?
+            .map(|x| bincode::deserialize::<Wallet>(&x).context("Invalid wallet format"))
+            .transpose()
+    }
+
+    /// Fail if wallet `name` exists and was created on other network than the minter uses
+    pub fn check_wallet_network(&self, name: &str) -> anyhow::Result<()> {
+        let Some(wallet) = self.get_wallet(name)? else { return Ok(()) };
+        if wallet.network != self.network {
+            bail!("Wallet {name} belongs to {}, but {} is used. Run with --network {}", wallet.network, self.network, wallet.network);
+        }
+        Ok(())
+    }
+
+    /// Fail if `address` belongs to other network than the minter uses
+    pub fn check_address(&self, address: &bitcoin::Address) -> anyhow::Result<()> {
+        self.network.check_address(address)
     }
 
     pub fn wallets<'a: 'b, 'b>(&'a self) -> anyhow::Result<impl Iterator<Item = Wallet> + 'b> {
         let iter = self.db
-            .iterate(self.tables.wallets.table(), vec![])
+            .iterate(&self.tables.wallets.table()?, vec![])
             .context("Failed to query wallets")?
             .filter_map(|(_,val)| {
                 let Ok(data) = bincode::deserialize::<Wallet>(&val) else {
@@ -56,5 +93,130 @@ impl Minter {
 
         Ok(iter)
     }
+
+    /// Fail if wallet `name` is watch-only, so it can't sign transactions
+    pub fn check_can_sign(&self, name: &str) -> anyhow::Result<()> {
+        if self.get_wallet(name)?.is_some_and(|x| x.is_watch_only()) {
+            bail!("Wallet #{name} is watch-only and can't sign. Create unsigned PSBT with `wallet psbt create` and sign it with wallet holding keys");
+        }
+        Ok(())
+    }
+
+    /// Derive key of encrypted wallet from password and keep it until exit. Does nothing for plain wallets
+    pub fn unlock_wallet(&self, name: &str, password: impl FnOnce() -> anyhow::Result<String>) -> anyhow::Result<()> {
+        let wallet = self.get_wallet(name)?.context("Wallet not found")?;
+        let WalletSecrets::Encrypted { kdf, data } = &wallet.secrets else { return Ok(()) };
+        if self.keys.lock().contains_key(name) { return Ok(()) }
+
+        let key = kdf.derive(&password()?)?;
+        key.decrypt(data, name.as_bytes()).context("Failed to unlock wallet")?;
+        self.keys.lock().insert(name.to_owned(), Arc::new(key));
+        Ok(())
+    }
+
+    fn wallet_key(&self, name: &str) -> anyhow::Result<Arc<WalletKey>> {
+        self.keys.lock().get(name).cloned().with_context(|| format!("Wallet #{name} is encrypted and locked"))
+    }
+
+    /// Get (mnemonic, passphrase) of wallet. Encrypted wallet must be unlocked
+    pub fn wallet_secrets(&self, wallet: &Wallet) -> anyhow::Result<(String, Option<String>)> {
+        match &wallet.secrets {
+            WalletSecrets::Plain { mnemonic, passphrase } => Ok((mnemonic.clone(), passphrase.clone())),
+            WalletSecrets::Encrypted { data, .. } => self.wallet_key(&wallet.name)?.decrypt_value(data, wallet.name.as_bytes()),
+            WalletSecrets::WatchOnly { .. } => bail!("Wallet #{} is watch-only and has no secrets", wallet.name),
+        }
+    }
+
+    /// BIP39 seed of wallet. Encrypted wallet must be unlocked
+    pub fn wallet_seed(&self, wallet: &Wallet) -> anyhow::Result<[u8; 64]> {
+        let (mnemonic, passphrase) = self.wallet_secrets(wallet)?;
+        let mnemonic = bip39::Mnemonic::from_str(&mnemonic).context("Invalid mnemonic is saved in DB")?;
+        Ok(mnemonic.to_seed(passphrase.as_deref().unwrap_or("bells")))
+    }
+
+    /// Get private key of address. Encrypted wallet must be unlocked
+    pub fn address_private(&self, wallet: &str, addr: &str, data: &WalletAddressData) -> anyhow::Result<Option<SecretKey>> {
+        if let Some(private) = data.private { return Ok(Some(private)); }
+        let Some(encrypted) = &data.encrypted_private else { return Ok(None) };
+        self.wallet_key(wallet)?.decrypt_value(encrypted, address_aad(wallet, addr).as_bytes()).map(Some)
+    }
+
+    /// Encrypt private key of address if wallet is encrypted
+    pub(crate) fn protect_address(&self, wallet: &str, addr: &str, mut data: WalletAddressData) -> anyhow::Result<WalletAddressData> {
+        let Some(private) = data.private else { return Ok(data) };
+        if !self.get_wallet(wallet)?.is_some_and(|x| x.is_encrypted()) { return Ok(data) }
+
+        data.encrypted_private = Some(self.wallet_key(wallet)?.encrypt_value(&private, address_aad(wallet, addr).as_bytes())?);
+        data.private = None;
+        Ok(data)
+    }
+
+    /// Encrypt mnemonic, passphrase and private keys of wallet with key derived from `password`
+    pub fn encrypt_wallet(&self, name: &str, password: &str) -> anyhow::Result<()> {
+        let mut wallet = self.get_wallet(name)?.context("Wallet not found")?;
+        if wallet.is_encrypted() {
+            bail!("Wallet #{name} is already encrypted");
+        }
+        if wallet.is_watch_only() {
+            bail!("Wallet #{name} is watch-only and has no secrets to encrypt");
+        }
+        let secrets = self.wallet_secrets(&wallet)?;
+
+        let kdf = KdfParams::new_random();
+        let key = kdf.derive(password)?;
+        let data = key.encrypt_value(&secrets, name.as_bytes())?;
+        wallet.secrets = WalletSecrets::Encrypted { kdf, data };
+
+        // wallet is never saved as encrypted while keys of its addresses are plain
+        let mut batch = rocksdb::WriteBatch::default();
+        Database::batch_set(&mut batch, &self.tables.wallets.table()?, name.as_bytes(), &wallet)?;
+        for (addr, mut data) in self.addresses(name)?.filter(|(_, x)| x.private.is_some()) {
+            let private = data.private.take().expect("address with private key is filtered");
+            data.encrypted_private = Some(key.encrypt_value(&private, address_aad(name, &addr).as_bytes())?);
+            Database::batch_set(&mut batch, &self.tables.addresses.table()?, address_key(name, &addr), &data)?;
+        }
+        self.db.write_batch(batch).context("Failed to save encrypted wallet")?;
+        self.keys.lock().insert(name.to_owned(), Arc::new(key));
+
+        // plain secrets stay in old DB files until they are compacted
+        self.db.purge_stale(&[&self.tables.wallets.table()?, &self.tables.addresses.table()?])
+            .context("Failed to remove plain secrets from DB files")?;
+
+        self.audit(name, AuditAction::Encrypt, None, "Encrypted wallet secrets");
+        info!("Wallet #{name} encrypted");
+        Ok(())
+    }
+
+    /// Remove encryption of wallet. Wallet must be unlocked
+    pub fn decrypt_wallet(&self, name: &str) -> anyhow::Result<()> {
+        let mut wallet = self.get_wallet(name)?.context("Wallet not found")?;
+        if !wallet.is_encrypted() {
+            bail!("Wallet #{name} is not encrypted");
+        }
+        let (mnemonic, passphrase) = self.wallet_secrets(&wallet)?;
+
+        let mut batch = rocksdb::WriteBatch::default();
+        for (addr, data) in self.addresses(name)?.filter(|(_, x)| x.encrypted_private.is_some()) {
+            let private = self.address_private(name, &addr, &data)?;
+            let data = WalletAddressData { private, encrypted_private: None, ..data };
+            Database::batch_set(&mut batch, &self.tables.addresses.table()?, address_key(name, &addr), &data)?;
+        }
+        wallet.secrets = WalletSecrets::Plain { mnemonic, passphrase };
+        Database::batch_set(&mut batch, &self.tables.wallets.table()?, name.as_bytes(), &wallet)?;
+        self.db.write_batch(batch).context("Failed to save decrypted wallet")?;
+        self.keys.lock().remove(name);
+
+        self.audit(name, AuditAction::Decrypt, None, "Removed encryption of wallet secrets");
+        info!("Wallet #{name} decrypted");
+        Ok(())
+    }
 }
 
+fn address_key(wallet: &str, addr: &str) -> String {
+    format!("{wallet}/{addr}")
+}
+
+/// Encrypted private key is bound to wallet and address it belongs to
+fn address_aad(wallet: &str, addr: &str) -> String {
+    address_key(wallet, addr)
+}
@@ -1,26 +1,157 @@
-use super::*;
+use {
+	super::*,
+	config::{Config, CONFIG_FILE},
+	minter::{audit::Redaction, backend::{BackendKind, ChainBackend, EsploraBackend, OfflineBackend, RpcAuth, RpcBackend}},
+};
 
 #[derive(Clone, Default, Debug, Parser)]
 pub struct Options {
-	#[clap(short, long, default_value = "bells", help = "Use wallet <WALLET>.")]
+	#[clap(short = 'w', long = "wallet", help = "Use wallet <WALLET>. [env: MINTER_WALLET] [default: bells]")]
+	pub(crate) wallet_argument: Option<String>,
+	#[clap(skip)]
 	pub(crate) wallet: String,
-	#[clap(long, default_value = "http://bells.quark.blue/api/", help = "Use API URL <API>.")]
+	#[clap(long = "network", value_enum, help = "Use <NETWORK>. Wallets can only be used on network they were created on. [env: MINTER_NETWORK] [default: mainnet]")]
+	pub(crate) network_argument: Option<Chain>,
+	#[clap(skip)]
+	pub(crate) network: Chain,
+	#[clap(long = "api-url", help = "Use API URL <API>. Defaults to API of --network. [env: MINTER_API_URL]")]
+	pub(crate) api_url_argument: Option<String>,
+	#[clap(skip)]
 	pub(crate) api_url: String,
+	#[clap(long = "backend", value_enum, help = "Get chain data from <BACKEND>. [env: MINTER_BACKEND] [default: esplora]")]
+	pub(crate) backend_argument: Option<BackendKind>,
+	#[clap(skip)]
+	pub(crate) backend: BackendKind,
+	#[clap(long, help = "Use bellsd JSON-RPC at <RPC_URL> with rpc backend. [env: MINTER_RPC_URL]")]
+	pub(crate) rpc_url: Option<String>,
+	#[clap(long, help = "Authenticate to bellsd as <RPC_USER>. [env: MINTER_RPC_USER]")]
+	pub(crate) rpc_user: Option<String>,
+	#[clap(long, help = "Authenticate to bellsd with <RPC_PASSWORD>. [env: MINTER_RPC_PASSWORD]")]
+	pub(crate) rpc_password: Option<String>,
+	#[clap(long, help = "Authenticate to bellsd with <RPC_COOKIE> file when user is not set. [env: MINTER_RPC_COOKIE]")]
+	pub(crate) rpc_cookie: Option<PathBuf>,
+	#[clap(long, help = "Refuse all network access, for signing on air-gapped machine. [env: MINTER_OFFLINE]")]
+	pub(crate) offline: bool,
+	#[clap(long = "data-dir", help = "Store DB in <DATA_DIR>. Defaults to per-user data dir. [env: MINTER_DATA_DIR]")]
+	pub(crate) data_dir_argument: Option<PathBuf>,
+	#[clap(skip)]
+	pub(crate) data_dir: PathBuf,
+	#[clap(long, help = "Load configuration from <CONFIG>. Defaults to bells.yaml in data dir. [env: MINTER_CONFIG]")]
+	pub(crate) config: Option<PathBuf>,
+	#[clap(long = "log-level", help = "Log with <LOG_LEVEL> filter. [env: MINTER_LOG_LEVEL] [default: debug]")]
+	pub(crate) log_level_argument: Option<String>,
+	#[clap(skip)]
+	pub(crate) log_level: String,
+	/// Fee rate used by commands when `--fee-rate` is not set
+	#[clap(skip)]
+	pub(crate) fee_rate: Option<FeeRate>,
+	#[clap(long = "redact", value_enum, help = "Keep <REDACT> out of audit log. With `--redact none`, new mnemonics, passphrases and private keys are also appended to the secrets file in plain text. [env: MINTER_REDACT] [default: secrets]")]
+	pub(crate) redact_argument: Option<Redaction>,
+	#[clap(skip)]
+	pub(crate) redact: Redaction,
+	#[clap(long = "secrets-file", help = "Append secrets to <SECRETS_FILE> with --redact none. [env: MINTER_SECRETS_FILE] [default: secrets.jsonl in data dir]")]
+	pub(crate) secrets_file_argument: Option<PathBuf>,
+	#[clap(skip)]
+	pub(crate) secrets_file: PathBuf,
 
 }
 
+fn env_var(name: &str) -> Option<String> {
+	env::var(name).ok().filter(|x| !x.is_empty())
+}
+
+fn env_value<T: clap::ValueEnum>(name: &str) -> Result<Option<T>> {
+	env_var(name)
+		.map(|x| T::from_str(&x, true).map_err(|_| anyhow!("Invalid {name} {x}")))
+		.transpose()
+}
+
 impl Options {
+	/// Fill settings not set on command line from environment, config file and defaults, in that order
+	pub(crate) fn resolve(&mut self) -> Result {
+		self.data_dir = match self.data_dir_argument.clone().or_else(|| env_var("MINTER_DATA_DIR").map(PathBuf::from)) {
+			Some(dir) => dir,
+			None => {
+				let dir = dirs::data_dir().context("Failed to find user data dir. Use --data-dir")?.join("bells");
+				// DB used to be in current directory. Starting with empty DB instead would hide its wallets
+				if !dir.join("db").exists() && Path::new("db").exists() {
+					bail!("Found DB in current directory, but {} is used by default now. Run with --data-dir . to keep using it, or move it there", dir.join("db").display());
+				}
+				dir
+			}
+		};
+		let config = self.load_config()?;
+
+		self.wallet = self.wallet_argument.clone()
+			.or_else(|| env_var("MINTER_WALLET"))
+			.or(config.wallet)
+			.unwrap_or_else(|| "bells".to_owned());
+
+		self.network = self.network_argument.or(env_value("MINTER_NETWORK")?).or(config.network).unwrap_or_default();
+
+		self.api_url = self.api_url_argument.clone()
+			.or_else(|| env_var("MINTER_API_URL"))
+			.or(config.api_url)
+			.unwrap_or_else(|| self.network.default_api_url().to_owned());
+
+		self.backend = self.backend_argument.or(env_value("MINTER_BACKEND")?).or(config.backend).unwrap_or_default();
+		self.rpc_url = self.rpc_url.take().or_else(|| env_var("MINTER_RPC_URL")).or(config.rpc_url);
+		self.rpc_user = self.rpc_user.take().or_else(|| env_var("MINTER_RPC_USER")).or(config.rpc_user);
+		self.rpc_password = self.rpc_password.take().or_else(|| env_var("MINTER_RPC_PASSWORD")).or(config.rpc_password);
+		self.rpc_cookie = self.rpc_cookie.take().or_else(|| env_var("MINTER_RPC_COOKIE").map(PathBuf::from)).or(config.rpc_cookie);
+		self.offline = self.offline || env_var("MINTER_OFFLINE").is_some_and(|x| x != "0" && x != "false") || config.offline.unwrap_or_default();
 
-	// pub(crate) fn load_config(&self) -> Result<Config> {
-	//   match &self.config {
-	//     Some(path) => Ok(serde_yaml::from_reader(File::open(path)?)?),
-	//     None => match &self.config_dir {
-	//       Some(dir) if dir.join("bells.yaml").exists() => {
-	//         Ok(serde_yaml::from_reader(File::open(dir.join("bells.yaml"))?)?)
-	//       }
-	//       Some(_) | None => Ok(Default::default()),
-	//     },
-	//   }
-	// }
-
-}
\ No newline at end of file
+		self.log_level = self.log_level_argument.clone()
+			.or_else(|| env_var("MINTER_LOG_LEVEL"))
+			.or(config.log_level)
+			.unwrap_or_else(|| "debug".to_owned());
+
+		self.redact = self.redact_argument.or(env_value("MINTER_REDACT")?).or(config.redact).unwrap_or_default();
+		self.secrets_file = self.secrets_file_argument.clone()
+			.or_else(|| env_var("MINTER_SECRETS_FILE").map(PathBuf::from))
+			.or(config.secrets_file)
+			.unwrap_or_else(|| self.data_dir.join("secrets.jsonl"));
+
+		self.fee_rate = match env_var("MINTER_FEE_RATE") {
+			Some(rate) => Some(rate.parse().context("Invalid MINTER_FEE_RATE")?),
+			None => config.fee_rate.map(FeeRate::try_from).transpose().context("Invalid fee_rate in config")?,
+		};
+
+		Ok(())
+	}
+
+	/// Config from `--config` file, or `bells.yaml` in data dir if it exists
+	pub(crate) fn load_config(&self) -> Result<Config> {
+		match self.config.clone().or_else(|| env_var("MINTER_CONFIG").map(PathBuf::from)) {
+			Some(path) => Config::load(&path),
+			None => match self.data_dir.join(CONFIG_FILE) {
+				path if path.exists() => Config::load(&path),
+				_ => Ok(Default::default()),
+			},
+		}
+	}
+
+	/// Chain backend selected by `--backend`. No backend client is built in `--offline` mode
+	pub(crate) fn chain_backend(&self) -> Result<Arc<dyn ChainBackend>> {
+		if self.offline {
+			return Ok(Arc::new(OfflineBackend));
+		}
+		Ok(match self.backend {
+			BackendKind::Esplora => Arc::new(EsploraBackend::new(self.api_url.clone())?),
+			BackendKind::Rpc => {
+				let url = self.rpc_url.clone().context("Rpc backend requires --rpc-url")?;
+				let auth = match (&self.rpc_user, &self.rpc_cookie) {
+					(Some(user), _) => RpcAuth::UserPass(user.clone(), self.rpc_password.clone().unwrap_or_default()),
+					(None, Some(cookie)) => RpcAuth::Cookie(cookie.clone()),
+					(None, None) => RpcAuth::None,
+				};
+				Arc::new(RpcBackend::new(url, auth)?)
+			}
+		})
+	}
+
+	/// Fee rate of `--fee-rate` argument, environment or config
+	pub(crate) fn fee_rate(&self, argument: Option<FeeRate>) -> Result<FeeRate> {
+		argument.or(self.fee_rate).context("Fee rate is not set. Use --fee-rate or set fee_rate in config")
+	}
+}
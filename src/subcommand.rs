@@ -1,6 +1,8 @@
 use self::minter::Minter;
 
 use super::*;
+pub mod db;
+pub mod tx;
 pub mod wallet;
 
 
@@ -14,12 +16,23 @@ fn print_json(output: impl Serialize) -> Result {
 pub(crate) enum Subcommand {  
 	#[clap(subcommand, about = "Wallet commands")]
 	Wallet(wallet::Wallet),
+	#[clap(subcommand, about = "DB commands")]
+	Db(db::Db),
+	#[clap(subcommand, about = "Transaction commands")]
+	Tx(tx::Tx),
 }
 
 impl Subcommand {
 	pub(crate) async fn run(self, options: Options, state: Arc<Minter>) -> Result {
 		match self {      
 			Self::Wallet(wallet) => wallet.run(options, state).await,
+			Self::Db(db) => db.run(options, state).await,
+			Self::Tx(tx) => tx.run(options, state).await,
 		}
 	}
+
+	/// Pending DB migrations run on start. DB commands run them on their own
+	pub(crate) fn migrates_db(&self) -> bool {
+		!matches!(self, Self::Db(_))
+	}
 }
\ No newline at end of file
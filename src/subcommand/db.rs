@@ -0,0 +1,58 @@
+use {super::*, crate::data::migrate::{MigrationReport, SCHEMA_VERSION}};
+
+#[derive(Debug, Parser)]
+pub(crate) enum Db {
+	#[clap(about = "Run pending DB migrations")]
+	Migrate(Migrate),
+}
+
+impl Db {
+	pub(crate) async fn run(self, options: Options, state: Arc<Minter>) -> Result {
+		match self {
+			Self::Migrate(args) => args.run(options, state),
+		}
+	}
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Migrate {
+	#[clap(long, help = "Show pending migrations and rows they change without writing anything")]
+	pub(crate) dry_run: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MigrateOutput {
+	pub version: u32,
+	pub target_version: u32,
+	pub dry_run: bool,
+	pub migrations: Vec<MigrationOutput>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MigrationOutput {
+	pub version: u32,
+	pub description: String,
+	#[serde(flatten)]
+	pub report: MigrationReport,
+}
+
+impl Migrate {
+	pub(crate) fn run(self, _options: Options, state: Arc<Minter>) -> Result {
+		let version = state.db.schema_version()?;
+		let migrations = state.db.migrate(self.dry_run).context("Failed to migrate DB")?
+			.into_iter()
+			.map(|(migration, report)| MigrationOutput {
+				version: migration.version,
+				description: migration.description.to_owned(),
+				report,
+			})
+			.collect();
+
+		print_json(MigrateOutput {
+			version,
+			target_version: SCHEMA_VERSION,
+			dry_run: self.dry_run,
+			migrations,
+		})
+	}
+}
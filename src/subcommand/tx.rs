@@ -0,0 +1,55 @@
+use {super::*, crate::minter::{audit::AuditAction, psbt::extract_psbt_tx}, bitcoin::psbt::Psbt};
+
+#[derive(Debug, Parser)]
+pub(crate) enum Tx {
+	#[clap(about = "Broadcast signed transaction")]
+	Broadcast(Broadcast),
+}
+
+impl Tx {
+	pub(crate) async fn run(self, options: Options, state: Arc<Minter>) -> Result {
+		match self {
+			Self::Broadcast(args) => args.run(options, state).await,
+		}
+	}
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Broadcast {
+	#[clap(help = "Hex of signed transaction or base64 finalized PSBT, or file with either of them")]
+	pub(crate) transaction: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct BroadcastOutput {
+	pub txid: Txid,
+}
+
+impl Broadcast {
+	pub(crate) async fn run(self, options: Options, state: Arc<Minter>) -> Result {
+		let data = match Path::new(&self.transaction).is_file() {
+			true => fs::read(&self.transaction).with_context(|| format!("Failed to read transaction file {}", self.transaction))?,
+			false => self.transaction.clone().into_bytes(),
+		};
+		// binary PSBT, as `read_psbt` reads it. Text is transaction hex or base64 PSBT
+		let tx = if data.starts_with(b"psbt\xff") {
+			extract_psbt_tx(consensus::deserialize::<Psbt>(&data).context("Invalid PSBT file")?)?
+		} else {
+			let text = String::from_utf8_lossy(&data);
+			match hex::decode(text.trim()) {
+				Ok(bytes) => consensus::deserialize::<Transaction>(&bytes).context("Invalid transaction")?,
+				Err(_) => extract_psbt_tx(text.trim().parse::<Psbt>().context("Neither transaction hex nor base64 PSBT")?)?,
+			}
+		};
+
+		let txid = state.broadcast_tx(&tx).await.context("Failed to broadcast transaction")?;
+		// spent outputs of current wallet must not be selected again
+		let spent = state.remove_spent_by(&options.wallet, &tx)?;
+		if spent > 0 {
+			state.audit(&options.wallet, AuditAction::Broadcast, Some(txid), format!("Broadcast transaction spending {spent} utxo's"));
+		}
+		info!("Broadcast transaction {txid}");
+
+		print_json(BroadcastOutput { txid })
+	}
+}
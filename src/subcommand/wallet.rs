@@ -15,12 +15,22 @@ use {
  tokio::runtime,
 };
 
+pub mod audit;
 pub mod balance;
+pub mod bump_fee;
 pub mod create;
+pub mod derive;
+pub mod freeze;
+pub mod import_addresses;
+pub mod inscribe;
+pub mod inscriptions;
 pub mod restore;
 pub mod receive;
 pub mod transactions;
 pub mod list_utxo;
+pub mod message;
+pub mod psbt;
+pub mod lock;
 pub mod util_commands;
 pub mod send;
 
@@ -30,15 +40,17 @@ pub mod send;
 #[derive(Debug, Parser)]
 pub(crate) enum Wallet {
 	#[clap(about = "Get wallet balance")]
-	Balance,
+	Balance(balance::Balance),
 	#[clap(about = "Create new wallet")]
 	Create(create::Create),
-	//#[clap(about = "Create inscription")]
-	//Inscribe(inscribe::Inscribe),
-	//#[clap(about = "List wallet inscriptions")]
-	//Inscriptions,
+	#[clap(about = "Create inscription")]
+	Inscribe(inscribe::Inscribe),
+	#[clap(about = "List wallet inscriptions")]
+	Inscriptions(inscriptions::Inscriptions),
 	#[clap(about = "Generate receive address")]
 	Receive(ReceiveArgs),
+	#[clap(about = "Derive address with specific index")]
+	Derive(derive::Derive),
 	
 	#[clap(about = "Get utxo's of address", name="utxo")]
 	ListUtxo(list_utxo::ListUtxo),
@@ -53,14 +65,40 @@ pub(crate) enum Wallet {
 	GetPrivate(util_commands::GetPrivate),
 	#[clap(about = "Import wallet", name="import")]
 	Import(util_commands::ImportYaml),
+	#[clap(about = "Import watch-only addresses")]
+	ImportAddresses(import_addresses::ImportAddresses),
+	#[clap(about = "Show account xpub for watch-only copy of wallet")]
+	Xpub(derive::Xpub),
+	#[clap(about = "Sign message with key of wallet address to prove its ownership")]
+	SignMessage(message::SignMessage),
+	#[clap(about = "Verify message signed by address")]
+	VerifyMessage(message::VerifyMessage),
 	#[clap(about = "Send")]
 	Send(send::Send),
-//   #[clap(about = "Restore wallet")]
-//   Restore(restore::Restore),
+	#[clap(about = "Bump fee of unconfirmed transaction with RBF or CPFP")]
+	BumpFee(bump_fee::BumpFee),
+	#[clap(subcommand, about = "PSBT commands for sending with review before signing")]
+	Psbt(psbt::PsbtCommand),
+	#[clap(about = "Forbid spending of outpoint")]
+	Freeze(freeze::Freeze),
+	#[clap(about = "Allow spending of frozen outpoint")]
+	Unfreeze(freeze::Unfreeze),
+	#[clap(about = "List frozen outpoints", name="frozen")]
+	ListFrozen(freeze::ListFrozen),
+	#[clap(about = "Encrypt wallet secrets with password")]
+	Lock(lock::Lock),
+	#[clap(about = "Remove password encryption of wallet secrets")]
+	Unlock(lock::Unlock),
+	#[clap(about = "Encrypt secrets of all not encrypted wallets in DB with password")]
+	Encrypt(lock::Encrypt),
+	#[clap(about = "Show audit log of wallet actions")]
+	Audit(audit::Audit),
+	#[clap(about = "Restore wallet from mnemonic or rescan its addresses")]
+	Restore(restore::Restore),
 	//#[clap(about = "Send sat or inscription")]
 	//Send(send::Send),
-	// #[clap(about = "See wallet transactions")]
-	// Transactions(transactions::Transactions),
+	#[clap(about = "See wallet transactions")]
+	Transactions(transactions::Transactions),
 	//#[clap(about = "List wallet outputs")]
 	//Outputs,
 }
@@ -68,26 +106,68 @@ pub(crate) enum Wallet {
 impl Wallet {
 	pub(crate) async fn run(self, options: Options, state: Arc<Minter>) -> Result<()> {
 		//let rt = runtime::Runtime::new().map_err(|e| Error::msg(e.to_string()))?;
+		state.check_wallet_network(&options.wallet)?;
 
 		match self {
-			Self::Balance => balance::run(options, state).await,
+			Self::Balance(args) => args.run(options, state).await,
 			Self::Create(create) => create.run(options, state),
-			//Self::Inscribe(inscribe) => inscribe.run(options),
-			//Self::Inscriptions => inscriptions::run(options),
+			Self::Inscribe(inscribe) => inscribe.run(options, state).await,
+			Self::Inscriptions(args) => args.run(options, state).await,
 			Self::Receive(args) => receive::run(options, state, args),
-			//Self::Restore(restore) => restore.run(options),
+			Self::Derive(args) => args.run(options, state).await,
+			Self::Restore(restore) => restore.run(options, state).await,
 			//Self::Send(send) => send.run(options),
 			Self::ListUtxo(args) => args.run(options, state).await,
 			Self::AddAddress(args) => args.run(options, state).await,
 			Self::RemoveAddress(args) => args.run(options, state).await,
 			Self::ListAddresses(args) => args.run(options, state).await,
 			Self::Send(args) => args.run(options, state).await,
+			Self::BumpFee(args) => args.run(options, state).await,
+			Self::Psbt(args) => args.run(options, state).await,
 			Self::GetPrivate(args) => args.run(options, state).await,
 			Self::Import(args) => args.run(options, state).await,
+			Self::ImportAddresses(args) => args.run(options, state).await,
+			Self::Xpub(args) => args.run(options, state).await,
+			Self::SignMessage(args) => args.run(options, state).await,
+			Self::VerifyMessage(args) => args.run(options, state).await,
+			Self::Freeze(args) => args.run(options, state).await,
+			Self::Unfreeze(args) => args.run(options, state).await,
+			Self::ListFrozen(args) => args.run(options, state).await,
+			Self::Lock(args) => args.run(options, state).await,
+			Self::Unlock(args) => args.run(options, state).await,
+			Self::Encrypt(args) => args.run(options, state).await,
+			Self::Audit(args) => args.run(options, state).await,
+			Self::Transactions(args) => args.run(options, state).await,
 			//Self::Outputs => outputs::run(options),
 		}
 	}
 }
 
+/// Environment variable with password of encrypted wallets. Prompted if not set
+const PASSWORD_ENV: &str = "MINTER_WALLET_PASSWORD";
 
+fn read_password() -> Result<String> {
+	if let Ok(password) = env::var(PASSWORD_ENV) {
+		return Ok(password);
+	}
+	rpassword::prompt_password("Wallet password: ").context("Failed to read password")
+}
+
+fn read_new_password() -> Result<String> {
+	if let Ok(password) = env::var(PASSWORD_ENV) {
+		return Ok(password);
+	}
+	let password = rpassword::prompt_password("New wallet password: ").context("Failed to read password")?;
+	if password.is_empty() {
+		bail!("Password can't be empty");
+	}
+	if rpassword::prompt_password("Repeat password: ").context("Failed to read password")? != password {
+		bail!("Passwords do not match");
+	}
+	Ok(password)
+}
 
+/// Unlock wallet of `options` if it is encrypted, so its secrets can be used
+pub(crate) fn unlock(options: &Options, state: &Minter) -> Result {
+	state.unlock_wallet(&options.wallet, read_password)
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use crate::{minter::{audit::AuditAction, Minter}, subcommand::print_json};
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct AuditOutput {
+    pub entries: Vec<AuditOutputItem>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct AuditOutputItem {
+    pub time: String,
+    pub wallet: String,
+    pub action: AuditAction,
+    pub txid: Option<bitcoin::Txid>,
+    pub details: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Audit {
+    #[clap(long, help = "Show entries of all wallets instead of current one")]
+    pub all_wallets: bool,
+    #[clap(long, help = "Show only entries of <ACTION>")]
+    pub action: Option<AuditAction>,
+    #[clap(long, help = "Show only entries with transaction <TXID>")]
+    pub txid: Option<bitcoin::Txid>,
+    #[clap(long, help = "Show only last <LIMIT> entries")]
+    pub limit: Option<usize>,
+}
+
+impl Audit {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let wallet = (!self.all_wallets).then_some(options.wallet.as_str());
+        let entries = state.audit_entries(wallet)?
+            .into_iter()
+            .filter(|x| self.action.is_none_or(|action| x.action == action))
+            .filter(|x| self.txid.is_none_or(|txid| x.txid == Some(txid)))
+            .collect::<Vec<_>>();
+        let skip = self.limit.map_or(0, |limit| entries.len().saturating_sub(limit));
+
+        let entries = entries.into_iter()
+            .skip(skip)
+            .map(|x| AuditOutputItem {
+                time: chrono::TimeZone::timestamp_millis_opt(&chrono::Utc, x.timestamp).single().map(|t| t.to_rfc3339()).unwrap_or_default(),
+                wallet: x.wallet,
+                action: x.action,
+                txid: x.txid,
+                details: x.details,
+            })
+            .collect();
+        print_json(AuditOutput { entries })
+    }
+}
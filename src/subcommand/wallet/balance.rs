@@ -1,77 +1,112 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use reqwest::StatusCode;
-use anyhow::{Result, Context, bail};
-use tracing::{debug, info};
+use anyhow::{Context, Result, bail};
+use itertools::Itertools;
+use tracing::debug;
 
-use crate::{ minter::Minter, subcommand::print_json};
+use crate::{minter::Minter, subcommand::print_json, wallet::AddressType};
 
 
-#[derive(serde::Serialize, serde::Deserialize)]
-pub struct Output {
-    pub cardinal: f64,
-    pub ordinal: f64,
+/// Balance in sats
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OutputBalance {
+    pub confirmed: u64,
+    /// Received by transactions in mempool
+    pub unconfirmed_incoming: u64,
+    /// Spent by transactions in mempool. Not known with cached data
+    pub unconfirmed_outgoing: u64,
+    /// Value of utxo's carrying inscriptions
+    pub inscribed: u64,
+    /// Value not locked in inscriptions, including unconfirmed transactions
+    pub cardinal: u64,
+}
+impl OutputBalance {
+    fn add(&mut self, other: &OutputBalance) {
+        self.confirmed += other.confirmed;
+        self.unconfirmed_incoming += other.unconfirmed_incoming;
+        self.unconfirmed_outgoing += other.unconfirmed_outgoing;
+        self.inscribed += other.inscribed;
+        self.cardinal += other.cardinal;
+    }
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
-pub struct ApiChainStats {
-    pub funded_txo_count: u64,
-    pub funded_txo_sum: u64,
-    pub spent_txo_count: u64,
-    pub spent_txo_sum: u64,
-    pub tx_count: u64,
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct OutputAddress {
+    pub address: String,
+    #[serde(rename="type")] pub ty: AddressType,
+    #[serde(flatten)] pub balance: OutputBalance,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
-pub struct ApiMempoolStats {
-    pub funded_txo_count: u64,
-    pub funded_txo_sum: u64,
-    pub spent_txo_count: u64,
-    pub spent_txo_sum: u64,
-    pub tx_count: u64,
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Output {
+    #[serde(flatten)] pub total: OutputBalance,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addresses: Option<Vec<OutputAddress>>,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
-pub struct ApiAddress {
-    pub address: String,
-    pub chain_stats: ApiChainStats,
-    pub mempool_stats: ApiMempoolStats,
+#[derive(Debug, clap::Parser)]
+pub struct Balance {
+    #[arg(short, help = "True to use only saved to DB data. False to fetch from api (and save to DB)")]
+    pub cached: bool,
+
+    #[arg(long, help = "Show balance of every wallet address")]
+    pub per_address: bool,
 }
 
+impl Balance {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> Result<()> {
+        let mut addresses = state.addresses(&options.wallet)?
+            .map(|(address, data)| OutputAddress { address, ty: data.ty, balance: OutputBalance::default() })
+            .collect_vec();
+        let position = addresses.iter().enumerate().map(|(i,x)| (x.address.clone(), i)).collect::<HashMap<_,_>>();
 
+        let utxo = if self.cached {
+            state.get_all_utxo(&options.wallet, |_,_| true).context("Failed to load utxo's")?
+        } else {
+            let stats = state.fetch_addresses("balance", addresses.iter().map(|x| x.address.clone()).collect(), |backend, address| Box::pin(async move {
+                backend.address_stats(&address).await
+            })).await?;
 
-pub(crate) async fn run(options: crate::subcommand::Options, state: Arc<Minter>) -> Result<()> {
-    let mut balance_utxo_sat = 0;
-    let mut balance_ord_sat = 0;
-    for (pub_key, addr) in state.addresses(&options.wallet)? {
-        debug!("Checking balance of address {pub_key}");
-        let url = format!("{}/address/{}", &options.api_url.trim_end_matches('/'), &pub_key);
-        let resp = state.reqwest_client.get(url).send().await.context("Failed to send api get balance request")?;
-        match resp.status() {
-            StatusCode::OK => {
-                let addr_data = resp.json::<ApiAddress>().await.context("Api get balance invalid json")?;
-                if addr_data.chain_stats.funded_txo_sum < addr_data.chain_stats.spent_txo_sum {
+            for ((address, stats), output) in stats.into_iter().zip(&mut addresses) {
+                if stats.chain_stats.funded_txo_sum < stats.chain_stats.spent_txo_sum {
                     bail!("Api is insane! Funded is less than spent!");
                 }
-                //todo: add from mempool?
-                let addr_balance = addr_data.chain_stats.funded_txo_sum - addr_data.chain_stats.spent_txo_sum;
-                info!("Address {pub_key} balance: {addr_balance}");
+                output.balance.confirmed = stats.chain_stats.funded_txo_sum - stats.chain_stats.spent_txo_sum;
+                output.balance.unconfirmed_incoming = stats.mempool_stats.funded_txo_sum;
+                output.balance.unconfirmed_outgoing = stats.mempool_stats.spent_txo_sum;
+                debug!("Address {address} balance: {:?}", output.balance);
+            }
 
-                match addr.ty {
-                    crate::wallet::AddressType::Utxo => balance_utxo_sat += addr_balance,
-                    crate::wallet::AddressType::Ord => balance_ord_sat += addr_balance,
-                }
+            // inscriptions are only known from utxo's
+            state.fetch_utxo(&options.wallet, |_,_| true, |_,_| true).await.context("Failed to fetch utxo's")?.0
+        };
+
+        for (address, x) in utxo.iter() {
+            let Some(&i) = position.get(address) else { continue };
+            let balance = &mut addresses[i].balance;
+            if x.inscription_meta.is_some() {
+                balance.inscribed += x.value;
             }
-            err => {
-                bail!("Api get balance error: {err}");
+            if self.cached {
+                match x.status.confirmed {
+                    true => balance.confirmed += x.value,
+                    false => balance.unconfirmed_incoming += x.value,
+                }
             }
         }
-    }
 
-    print_json(Output {
-        cardinal: bitcoin::Amount::from_sat(balance_utxo_sat).to_btc(),
-        ordinal: bitcoin::Amount::from_sat(balance_ord_sat).to_btc(),
-    }).unwrap();
-    
-    Ok(())
+        let mut total = OutputBalance::default();
+        for x in &mut addresses {
+            let balance = &mut x.balance;
+            balance.cardinal = (balance.confirmed + balance.unconfirmed_incoming).saturating_sub(balance.unconfirmed_outgoing + balance.inscribed);
+            total.add(balance);
+        }
+
+        print_json(Output {
+            total,
+            addresses: self.per_address.then_some(addresses),
+        }).unwrap();
+
+        Ok(())
+    }
 }
@@ -0,0 +1,25 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+
+use super::*;
+use crate::minter::Minter;
+
+#[derive(Debug, clap::Parser)]
+pub struct BumpFee {
+    #[arg(help = "Unconfirmed transaction of wallet")]
+    txid: Txid,
+    #[clap(long, help = "Use fee rate of <FEE_RATE> nook/vB")]
+    fee_rate: Option<FeeRate>,
+}
+
+impl BumpFee {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let fee_rate = options.fee_rate(self.fee_rate)?;
+        state.check_can_sign(&options.wallet)?;
+        super::unlock(&options, &state)?;
+        let result = state.bump_fee(&options.wallet, &self.txid, fee_rate).await.context("Failed to bump fee")?;
+
+        print_json(result)
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use crate::minter::Minter;
 use crate::subcommand::print_json;
 use anyhow::Context;
+use bitcoin::util::bip32::ExtendedPubKey;
 
 #[derive(serde::Serialize)]
 struct Output {
@@ -11,18 +12,38 @@ struct Output {
 	passphrase: Option<String>,
 }
 
+#[derive(serde::Serialize)]
+struct WatchOnlyOutput {
+	watch_only: bool,
+	xpub: Option<ExtendedPubKey>,
+}
+
 #[derive(Debug, clap::Parser)]
 pub(crate) struct Create {
 	#[clap(long, default_value = "bells", help = "Use <PASSPHRASE> to derive wallet seed.")] 
 	pub(crate) passphrase: String,
+	#[clap(long, help = "Create wallet without keys, which can't sign transactions.")]
+	pub(crate) watch_only: bool,
+	#[clap(long, requires = "watch_only", help = "Derive addresses of watch-only wallet from account <XPUB>. Addresses are imported with `wallet import-addresses` otherwise.")]
+	pub(crate) xpub: Option<ExtendedPubKey>,
 }
 
 impl Create {
 	pub(crate) fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+		if self.watch_only {
+			state.create_watch_only_wallet(options.wallet, self.xpub).context("Failed to create wallet")?;
+			return print_json(WatchOnlyOutput {
+				watch_only: true,
+				xpub: self.xpub,
+			});
+		}
+
 		let wallet = state.create_wallet(self.passphrase.clone(), options.wallet).context("Failed to create wallet")?;
 
+		let (mnemonic, _) = state.wallet_secrets(&wallet)?;
+
 		print_json(Output {
-			mnemonic: bip39::Mnemonic::from_str(&wallet.mnemonic).unwrap(),
+			mnemonic: bip39::Mnemonic::from_str(&mnemonic).context("Invalid mnemonic")?,
 			passphrase: Some(self.passphrase),
 		})?;
 
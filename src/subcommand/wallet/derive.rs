@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use bitcoin::util::bip32::{DerivationPath, ExtendedPubKey};
+
+use crate::{minter::{derivation::account_path, Minter}, subcommand::print_json, wallet::{AddressType, WalletAddressData}};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Output {
+    pub address: bitcoin::Address,
+    pub path: DerivationPath,
+    #[serde(rename="type")] pub ty: AddressType,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Derive {
+    #[clap(long, help = "Derive address with child index <INDEX>")]
+    pub index: u32,
+    #[clap(long = "type", value_name = "TYPE", help = "Derive address of utxo or ord chain")]
+    pub ty: AddressType,
+}
+
+impl Derive {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        super::unlock(&options, &state)?;
+        let wallet = state.get_wallet(&options.wallet)?.context("Wallet not found")?;
+
+        let derived = state.derive_address(&wallet, self.ty, self.index)?;
+        let address = derived.address.to_string();
+        if state.get_address(&options.wallet, &address)?.is_none() {
+            state.push_address(&address, &WalletAddressData::derived(derived.private, self.ty, derived.path.clone()), &options.wallet)?;
+        }
+
+        print_json(Output {
+            address: derived.address,
+            path: derived.path,
+            ty: self.ty,
+        })
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct XpubOutput {
+    pub xpub: ExtendedPubKey,
+    pub path: DerivationPath,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Xpub {
+}
+
+impl Xpub {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        super::unlock(&options, &state)?;
+        let wallet = state.get_wallet(&options.wallet)?.context("Wallet not found")?;
+
+        print_json(XpubOutput {
+            xpub: state.account_xpub(&wallet)?,
+            path: account_path(wallet.network),
+        })
+    }
+}
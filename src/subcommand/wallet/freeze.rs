@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use crate::{minter::Minter, subcommand::print_json};
+
+#[derive(Debug, clap::Parser)]
+pub struct Freeze {
+    #[arg(help = "outpoint <TXID>:<VOUT> which must not be spent")]
+    pub outpoint: bitcoin::OutPoint,
+}
+
+impl Freeze {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        state.freeze_outpoint(&options.wallet, &self.outpoint)
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Unfreeze {
+    #[arg(help = "frozen outpoint <TXID>:<VOUT>")]
+    pub outpoint: bitcoin::OutPoint,
+}
+
+impl Unfreeze {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        state.unfreeze_outpoint(&options.wallet, &self.outpoint)
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct ListFrozenOutput {
+    pub frozen: Vec<bitcoin::OutPoint>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListFrozen {
+}
+
+impl ListFrozen {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let mut frozen = state.frozen_outpoints(&options.wallet)?.into_iter().collect::<Vec<_>>();
+        frozen.sort();
+        print_json(ListFrozenOutput { frozen })
+    }
+}
@@ -0,0 +1,58 @@
+use std::{str::FromStr, sync::Arc};
+
+use anyhow::Context;
+
+use crate::{minter::Minter, subcommand::print_json, wallet::{AddressType, WalletAddressData}};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Output {
+    pub imported: Vec<String>,
+    /// Addresses which are already in wallet
+    pub skipped: usize,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ImportAddresses {
+    #[arg(help = "public wallet addresses")]
+    pub addresses: Vec<String>,
+    #[clap(long, help = "Import addresses listed one per line in <FILE>. Empty lines and lines starting with # are ignored")]
+    pub file: Option<String>,
+    #[clap(long = "type", value_name = "TYPE", default_value = "utxo", help = "Import addresses as utxo or ord")]
+    pub ty: AddressType,
+}
+
+impl ImportAddresses {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let mut addresses = self.addresses;
+        if let Some(file) = &self.file {
+            let list = std::fs::read_to_string(file).with_context(|| format!("Failed to read {file}"))?;
+            addresses.extend(list.lines().map(str::trim).filter(|x| !x.is_empty() && !x.starts_with('#')).map(str::to_owned));
+        }
+        if addresses.is_empty() {
+            anyhow::bail!("No addresses given");
+        }
+        // validate all before saving any
+        let addresses = addresses.iter()
+            .map(|x| bitcoin::Address::from_str(x).with_context(|| format!("Invalid address {x}")).and_then(|x| state.check_address(&x).map(|_| x)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        if state.get_wallet(&options.wallet)?.is_none() {
+            info!("Wallet #{} not found. Creating watch-only wallet", options.wallet);
+            state.create_watch_only_wallet(options.wallet.clone(), None)?;
+        }
+
+        let total = addresses.len();
+        let mut imported = vec![];
+        for address in addresses {
+            let address = address.to_string();
+            if state.get_address(&options.wallet, &address)?.is_some() || imported.contains(&address) { continue; }
+            state.push_address(&address, &WalletAddressData::new(None, self.ty), &options.wallet)?;
+            imported.push(address);
+        }
+
+        print_json(Output {
+            skipped: total - imported.len(),
+            imported,
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use super::*;
+use crate::{minter::{audit::AuditAction, inscribe::{reveal_txid, Inscription}, Minter}, wallet::AddressType};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Output {
+    pub inscription: InscriptionId,
+    pub transactions: Vec<Txid>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct DryRunOutput {
+    pub inscription: InscriptionId,
+    pub transactions: Vec<String>,
+}
+
+/// Printed when broadcast stops in the middle of chain of transactions
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct FailedOutput {
+    pub inscription: InscriptionId,
+    /// Transactions which were broadcast
+    pub transactions: Vec<Txid>,
+    /// Hex of signed transactions left, to broadcast in order with `tx broadcast`
+    pub remaining: Vec<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Inscribe {
+    #[arg(help = "Inscribe sat with contents of <FILE>")]
+    pub file: PathBuf,
+    #[clap(long, help = "Send inscription to <DESTINATION>. Defaults to first ord address of wallet")]
+    pub destination: Option<Address>,
+    #[clap(long, help = "Use <CONTENT_TYPE> instead of guessing it from file extension")]
+    pub content_type: Option<String>,
+    #[clap(long, help = "Use fee rate of <FEE_RATE> nook/vB")]
+    pub fee_rate: Option<FeeRate>,
+    #[clap(long, help = "Don't broadcast transactions, print them as hex")]
+    pub dry_run: bool,
+}
+
+impl Inscribe {
+    pub(crate) async fn run(self, options: Options, state: Arc<Minter>) -> Result {
+        let inscription = Inscription::from_file(&self.file, self.content_type)?;
+        let fee_rate = options.fee_rate(self.fee_rate)?;
+        super::unlock(&options, &state)?;
+
+        let dest = match self.destination {
+            Some(x) => x,
+            None => {
+                let (addr, _) = state.addresses(&options.wallet)?
+                    .find(|(_, x)| x.ty == AddressType::Ord)
+                    .context("Wallet has no ord addresses. Generate one with `wallet receive ord` or use --destination")?;
+                Address::from_str(&addr).context("Invalid ord address saved in DB")?
+            }
+        };
+
+        let (transactions, spent) = state.inscribe(&options.wallet, inscription, dest.clone(), fee_rate).await.context("Failed to create inscription")?;
+        let inscription = InscriptionId::from(reveal_txid(&transactions).context("No reveal transaction was created")?);
+
+        if self.dry_run {
+            return print_json(DryRunOutput {
+                inscription,
+                transactions: transactions.iter().map(consensus::encode::serialize_hex).collect(),
+            });
+        }
+
+        let mut txids = vec![];
+        for tx in &transactions {
+            match state.broadcast_tx(tx).await {
+                Ok(txid) => txids.push(txid),
+                Err(e) => {
+                    let failed = txids.len();
+                    // funding utxo's are spent as soon as the first transaction is out
+                    if failed > 0 {
+                        state.remove_spent_utxo(&options.wallet, &spent)?;
+                        state.audit(&options.wallet, AuditAction::Inscribe, txids.last().copied(),
+                            format!("Inscribing {inscription} to {dest} stopped after {failed} of {} transactions", transactions.len()));
+                    }
+                    print_json(FailedOutput {
+                        inscription,
+                        transactions: txids,
+                        remaining: transactions[failed..].iter().map(consensus::encode::serialize_hex).collect(),
+                    })?;
+                    return Err(e).with_context(|| format!("Failed to broadcast transaction {} of {}. Broadcast remaining ones with `tx broadcast`", failed + 1, transactions.len()));
+                }
+            }
+        }
+        state.remove_spent_utxo(&options.wallet, &spent)?;
+        state.audit(&options.wallet, AuditAction::Inscribe, txids.last().copied(), format!("Inscribed {inscription} to {dest} with {} transactions", txids.len()));
+
+        print_json(Output {
+            inscription,
+            transactions: txids,
+        })
+    }
+}
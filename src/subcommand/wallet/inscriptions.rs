@@ -0,0 +1,65 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+
+use crate::{minter::Minter, subcommand::print_json, InscriptionId};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Output {
+    pub inscriptions: Vec<OutputInscription>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct OutputInscription {
+    pub inscription: InscriptionId,
+    pub number: usize,
+    pub content_type: String,
+    pub content_length: usize,
+    pub genesis: bitcoin::OutPoint,
+    pub outpoint: bitcoin::OutPoint,
+    pub address: String,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Inscriptions {
+    #[arg(short, help = "True to use only saved to DB data. False to fetch from api (and save to DB)")]
+    pub cached: bool,
+
+    #[arg(long, help = "Show only inscriptions with content type starting with <CONTENT_TYPE> (e.g. image/)")]
+    pub content_type: Option<String>,
+
+    #[arg(long, help = "Show only inscriptions with number >= <MIN_NUMBER>")]
+    pub min_number: Option<usize>,
+
+    #[arg(long, help = "Show only inscriptions with number <= <MAX_NUMBER>")]
+    pub max_number: Option<usize>,
+}
+
+impl Inscriptions {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let utxo = if self.cached {
+            state.get_all_utxo(&options.wallet, |_,_| true).context("Failed to load utxo's")?
+        } else {
+            state.fetch_utxo(&options.wallet, |_,_| true, |_,_| true).await.context("Failed to fetch utxo's")?.0
+        };
+
+        let mut inscriptions = utxo
+            .iter()
+            .filter_map(|(addr, x)| x.inscription_meta.as_ref().map(|meta| (addr, x, meta)))
+            .filter(|(_, _, meta)| self.content_type.as_deref().is_none_or(|x| meta.content_type.starts_with(x)))
+            .filter(|(_, _, meta)| self.min_number.is_none_or(|x| meta.number >= x))
+            .filter(|(_, _, meta)| self.max_number.is_none_or(|x| meta.number <= x))
+            .map(|(addr, x, meta)| OutputInscription {
+                inscription: InscriptionId { txid: meta.inscription_id.txid, index: meta.inscription_id.index },
+                number: meta.number,
+                content_type: meta.content_type.clone(),
+                content_length: meta.content_length,
+                genesis: meta.genesis,
+                outpoint: bitcoin::OutPoint { txid: x.txid, vout: x.vout },
+                address: addr.to_owned(),
+            })
+            .collect::<Vec<_>>();
+        inscriptions.sort_by_key(|x| x.number);
+
+        print_json(Output { inscriptions })
+    }
+}
@@ -2,15 +2,25 @@ use std::sync::Arc;
 
 use anyhow::Context;
 
-use crate::{minter::{utxo::UtxoData, Minter}, subcommand::print_json, wallet::{AddressType, WalletAddressData}};
+use crate::{minter::{utxo::{UtxoData, UtxoDiff}, Minter}, subcommand::print_json, wallet::{AddressType, WalletAddressData}};
 
 
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct Output {
     utxo: Vec<OutputUtxoData>,
+    /// Changes since last sync. Not set with `--cached`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    changes: Option<OutputChanges>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct OutputChanges {
+    pub added: Vec<OutputUtxoData>,
+    pub spent: Vec<OutputUtxoData>,
+    pub status_changed: Vec<OutputUtxoData>,
 }
 #[derive(serde::Serialize, serde::Deserialize)]
 pub struct OutputUtxoData {
+    pub address: String,
     pub txid: String,
     pub vout: u32,
     pub confirmed: bool,
@@ -33,22 +43,32 @@ impl ListUtxo {
         let utxo_selector = |k: &str,x: &UtxoData|
             x.ty == AddressType::Utxo && (!self.address.as_deref().is_some_and(|addr| k.split('/').nth(1).unwrap() != addr));
 
-        let utxo = if self.cached {
-            state.get_all_utxo(&options.wallet, wallet_selector).context("Failed to load utxo's")?
+        let (utxo, diff) = if self.cached {
+            (state.get_all_utxo(&options.wallet, wallet_selector).context("Failed to load utxo's")?, None)
         } else {
-            state.fetch_utxo(&options.wallet, wallet_selector, utxo_selector).await.context("Failed to fetch utxo's")?
+            let (utxo, diff) = state.fetch_utxo(&options.wallet, wallet_selector, utxo_selector).await.context("Failed to fetch utxo's")?;
+            (utxo, Some(diff))
+        };
+
+        let to_output = |addr: &str, x: &UtxoData| OutputUtxoData {
+            address: addr.to_owned(),
+            txid: x.txid.to_string(),
+            vout: x.vout,
+            confirmed: x.status.confirmed,
+            value: bitcoin::Amount::from_sat(x.value).to_btc(),
         };
+        let changes = diff.map(|diff: UtxoDiff| {
+            let convert = |list: Vec<(String, UtxoData)>| list.iter().map(|(addr,x)| to_output(addr, x)).collect();
+            OutputChanges {
+                added: convert(diff.added),
+                spent: convert(diff.spent),
+                status_changed: convert(diff.status_changed),
+            }
+        });
 
         print_json(Output {
-            utxo: utxo
-                .iter()
-                .map(|(_addr,x)| OutputUtxoData {
-                    txid: x.txid.to_string(),
-                    vout: x.vout,
-                    confirmed: x.status.confirmed,
-                    value: bitcoin::Amount::from_sat(x.value).to_btc(),
-                })
-                .collect(),
+            utxo: utxo.iter().map(|(addr,x)| to_output(addr, x)).collect(),
+            changes,
         }).unwrap();
         Ok(())
     }
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+
+use crate::minter::Minter;
+
+use super::{read_new_password, unlock};
+
+#[derive(Debug, clap::Parser)]
+pub struct Lock {
+}
+
+impl Lock {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let password = read_new_password()?;
+        state.encrypt_wallet(&options.wallet, &password).context("Failed to encrypt wallet")?;
+        warn_old_copies();
+        Ok(())
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Unlock {
+}
+
+impl Unlock {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        unlock(&options, &state)?;
+        state.decrypt_wallet(&options.wallet).context("Failed to decrypt wallet")
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Encrypt {
+}
+
+impl Encrypt {
+    pub async fn run(self, _options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let wallets = state.wallets()?.filter(|x| !x.is_encrypted() && !x.is_watch_only()).map(|x| x.name).collect::<Vec<_>>();
+        if wallets.is_empty() {
+            info!("All wallets are already encrypted");
+            return Ok(());
+        }
+
+        let password = read_new_password()?;
+        for name in wallets {
+            state.encrypt_wallet(&name, &password).with_context(|| format!("Failed to encrypt wallet #{name}"))?;
+        }
+        warn_old_copies();
+        Ok(())
+    }
+}
+
+/// DB files are rewritten on encryption, but copies made earlier are not
+fn warn_old_copies() {
+    warn!("Copies and backups of data directory made before encryption still hold plain secrets. Delete them");
+}
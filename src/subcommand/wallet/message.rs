@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+use bitcoin::util::misc::MessageSignature;
+
+use crate::{minter::{message::verify_message, Minter}, subcommand::print_json};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SignOutput {
+    pub address: bitcoin::Address,
+    /// Base64 signature
+    pub signature: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct VerifyOutput {
+    pub address: bitcoin::Address,
+    pub valid: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SignMessage {
+    #[clap(long, help = "Sign with key of wallet <ADDRESS>")]
+    pub address: bitcoin::Address,
+    pub message: String,
+}
+
+impl SignMessage {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        super::unlock(&options, &state)?;
+        let signature = state.sign_message(&options.wallet, &self.address, &self.message).context("Failed to sign message")?;
+
+        print_json(SignOutput {
+            address: self.address,
+            signature: signature.to_base64(),
+        })
+    }
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct VerifyMessage {
+    pub address: bitcoin::Address,
+    #[arg(help = "Base64 signature")]
+    pub signature: MessageSignature,
+    pub message: String,
+}
+
+impl VerifyMessage {
+    pub async fn run(self, _options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        state.check_address(&self.address)?;
+
+        print_json(VerifyOutput {
+            valid: verify_message(&self.address, &self.signature, &self.message)?,
+            address: self.address,
+        })
+    }
+}
@@ -0,0 +1,181 @@
+use std::{fs, path::{Path, PathBuf}, sync::Arc};
+
+use anyhow::Context;
+use bitcoin::psbt::Psbt;
+
+use super::*;
+use crate::minter::{audit::AuditAction, psbt::{extract_psbt_tx, finalize_psbt, psbt_fee}, Minter};
+
+#[derive(Debug, Parser)]
+pub(crate) enum PsbtCommand {
+    #[clap(about = "Create unsigned PSBT of send, for review before signing")]
+    Create(Create),
+    #[clap(about = "Sign PSBT inputs of wallet addresses")]
+    Sign(Sign),
+    #[clap(about = "Combine signatures of PSBTs of the same transaction")]
+    Combine(Combine),
+    #[clap(about = "Finalize signed PSBT and extract its transaction")]
+    Finalize(Finalize),
+}
+
+impl PsbtCommand {
+    pub(crate) async fn run(self, options: Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        match self {
+            Self::Create(args) => args.run(options, state).await,
+            Self::Sign(args) => args.run(options, state),
+            Self::Combine(args) => args.run(),
+            Self::Finalize(args) => args.run(),
+        }
+    }
+}
+
+/// Read PSBT given as base64 or as file with base64 or binary PSBT, so air-gapped machines can pass files
+pub(crate) fn read_psbt(argument: &str) -> anyhow::Result<Psbt> {
+    if !Path::new(argument).is_file() {
+        return argument.trim().parse().context("Invalid base64 PSBT");
+    }
+    let data = fs::read(argument).with_context(|| format!("Failed to read PSBT file {argument}"))?;
+    if data.starts_with(b"psbt\xff") {
+        return bitcoin::consensus::deserialize(&data).with_context(|| format!("Invalid PSBT file {argument}"));
+    }
+    String::from_utf8_lossy(&data).trim().parse().with_context(|| format!("Invalid base64 PSBT file {argument}"))
+}
+
+/// Write `data` to `--output` file if it is set
+fn write_output(output: &Option<PathBuf>, data: &str) -> anyhow::Result<()> {
+    let Some(path) = output else { return Ok(()) };
+    fs::write(path, data).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Output {
+    /// Base64 PSBT
+    pub psbt: String,
+    pub txid: Txid,
+    /// Fee in sats
+    pub fee: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SignOutput {
+    pub psbt: String,
+    pub txid: Txid,
+    /// Inputs signed by wallet
+    pub signed: usize,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct FinalizeOutput {
+    pub psbt: String,
+    pub txid: Txid,
+    /// Hex of signed transaction, ready for `tx broadcast`
+    pub tx: String,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Create {
+    address: Address,
+    outgoing: Outgoing,
+    #[clap(long, help = "Use fee rate of <FEE_RATE> nook/vB")]
+    fee_rate: Option<FeeRate>,
+    #[clap(short, long, help = "Write base64 PSBT to <OUTPUT> file")]
+    output: Option<PathBuf>,
+}
+
+impl Create {
+    pub(crate) async fn run(self, options: Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let fee_rate = options.fee_rate(self.fee_rate)?;
+        let (tx, prevouts) = match &self.outgoing {
+            Outgoing::Amount(amount) => state.build_send_utxo(&options.wallet, &self.address, *amount, fee_rate).await,
+            Outgoing::InscriptionId(id) => state.build_send_inscription(&options.wallet, &self.address, id, fee_rate).await,
+        }.context("Failed to build transaction")?;
+        let psbt = state.create_psbt(tx, &prevouts).await?;
+
+        let txid = psbt.unsigned_tx.txid();
+        let what = match &self.outgoing {
+            Outgoing::Amount(amount) => amount.to_string(),
+            Outgoing::InscriptionId(id) => format!("inscription {id}"),
+        };
+        state.audit(&options.wallet, AuditAction::CreatePsbt, Some(txid), format!("Created PSBT sending {what} to {}", self.address));
+
+        let fee = psbt_fee(&psbt)?;
+        let psbt = psbt.to_string();
+        write_output(&self.output, &psbt)?;
+        print_json(Output { psbt, txid, fee })
+    }
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Sign {
+    #[clap(help = "Base64 PSBT or file with it")]
+    psbt: String,
+    #[clap(short, long, help = "Write base64 signed PSBT to <OUTPUT> file")]
+    output: Option<PathBuf>,
+}
+
+impl Sign {
+    pub(crate) fn run(self, options: Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let mut psbt = read_psbt(&self.psbt)?;
+        super::unlock(&options, &state)?;
+        let signed = state.sign_psbt(&options.wallet, &mut psbt).context("Failed to sign PSBT")?;
+        let txid = psbt.unsigned_tx.txid();
+        if signed == 0 {
+            warn!("PSBT has no inputs wallet {} can sign", options.wallet);
+        } else {
+            state.audit(&options.wallet, AuditAction::SignPsbt, Some(txid), format!("Signed {signed} inputs of PSBT"));
+        }
+
+        let psbt = psbt.to_string();
+        write_output(&self.output, &psbt)?;
+        print_json(SignOutput { psbt, txid, signed })
+    }
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Combine {
+    #[clap(required = true, num_args = 2.., help = "Base64 PSBTs of the same transaction or files with them")]
+    psbts: Vec<String>,
+    #[clap(short, long, help = "Write base64 combined PSBT to <OUTPUT> file")]
+    output: Option<PathBuf>,
+}
+
+impl Combine {
+    pub(crate) fn run(self) -> anyhow::Result<()> {
+        let mut psbts = self.psbts.iter().map(|x| read_psbt(x));
+        let mut psbt = psbts.next().context("No PSBT given")??;
+        for other in psbts {
+            psbt.combine(other?).context("Failed to combine PSBTs")?;
+        }
+
+        let fee = psbt_fee(&psbt)?;
+        let txid = psbt.unsigned_tx.txid();
+        let psbt = psbt.to_string();
+        write_output(&self.output, &psbt)?;
+        print_json(Output { psbt, txid, fee })
+    }
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Finalize {
+    #[clap(help = "Base64 signed PSBT or file with it")]
+    psbt: String,
+    #[clap(short, long, help = "Write hex of signed transaction to <OUTPUT> file")]
+    output: Option<PathBuf>,
+}
+
+impl Finalize {
+    pub(crate) fn run(self) -> anyhow::Result<()> {
+        let mut psbt = read_psbt(&self.psbt)?;
+        finalize_psbt(&mut psbt).context("Failed to finalize PSBT")?;
+        let finalized = psbt.to_string();
+        let tx = extract_psbt_tx(psbt)?;
+
+        let hex = bitcoin::consensus::encode::serialize_hex(&tx);
+        write_output(&self.output, &hex)?;
+        print_json(FinalizeOutput {
+            psbt: finalized,
+            txid: tx.txid(),
+            tx: hex,
+        })
+    }
+}
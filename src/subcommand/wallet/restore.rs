@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+
+use crate::{minter::{restore::{RestoredAddress, DEFAULT_GAP_LIMIT}, Minter}, subcommand::print_json};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Output {
+    pub addresses: Vec<RestoredAddress>,
+    pub utxo: usize,
+}
+
+#[derive(Debug, clap::Parser)]
+pub(crate) struct Restore {
+    #[clap(long, help = "Restore wallet from <MNEMONIC>. Rescans existing wallet if not set")]
+    pub(crate) mnemonic: Option<String>,
+    #[clap(long, default_value = "bells", help = "Use <PASSPHRASE> to derive wallet seed.")]
+    pub(crate) passphrase: String,
+    #[clap(long, default_value_t = DEFAULT_GAP_LIMIT, help = "Stop scanning chain after <GAP_LIMIT> consecutive unused addresses")]
+    pub(crate) gap_limit: u32,
+}
+
+impl Restore {
+    pub(crate) async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        match self.mnemonic {
+            Some(mnemonic) => {
+                state.create_wallet_with_mnemonic(self.passphrase, options.wallet.clone(), mnemonic).context("Failed to restore wallet")?;
+            }
+            None => super::unlock(&options, &state)?,
+        }
+
+        let addresses = state.restore_addresses(&options.wallet, self.gap_limit).await.context("Failed to scan addresses")?;
+        let (utxo, _) = state.fetch_utxo(&options.wallet, |_,_| true, |_,_| true).await.context("Failed to fetch utxo")?;
+        info!("Restored {} addresses of #{}", addresses.len(), options.wallet);
+
+        print_json(Output {
+            addresses,
+            utxo: utxo.len(),
+        })
+    }
+}
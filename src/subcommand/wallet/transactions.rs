@@ -1,41 +1,61 @@
-// use std::sync::Arc;
-
-// use anyhow::{Context, bail};
-// use reqwest::StatusCode;
-// use tracing::debug;
-
-// use crate::minter::Minter;
-
-// #[derive(serde::Serialize, serde::Deserialize)]
-// pub struct Output {
-//     transactions: Vec<String>,
-// }
-// #[derive(Debug, clap::Parser)]
-// pub struct Transactions {
-
-// }
-
-// mod get_transactions_api_types {
-//     pub struct 
-// }
-
-// impl Transactions {
-//     pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
-//         for (pub_key, addr) in state.addresses()? {
-//             debug!("Retrieving transactions of address {pub_key}");
-//             let url = format!("{}/address/{}/txs", &options.api_url.trim_end_matches('/'), &pub_key);
-//             let resp = state.reqwest_client.get(url).send().await.context("Failed to send api get transactions request")?;
-            
-//             match resp.status() {
-//                 StatusCode::OK => {
-                    
-//                 }
-//                 err => {
-//                     bail!("Api get transactions error: {err}");
-//                 }
-//             }
-//         }
-
-//         Ok(())
-//     }
-// }
\ No newline at end of file
+use std::sync::Arc;
+
+use anyhow::Context;
+
+use crate::{minter::{history::{tx_effect, TxKind}, Minter}, subcommand::print_json};
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Output {
+    pub transactions: Vec<OutputTransaction>,
+}
+
+/// Amounts are in sats
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct OutputTransaction {
+    pub txid: bitcoin::Txid,
+    pub kind: TxKind,
+    pub received: u64,
+    pub sent: u64,
+    pub fee: Option<u64>,
+    pub net: i64,
+    pub inscription_moved: bool,
+    pub confirmed: bool,
+    pub height: Option<usize>,
+    pub time: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct Transactions {
+    #[arg(short, help = "True to use only saved to DB data. False to fetch from api (and save to DB)")]
+    pub cached: bool,
+}
+
+impl Transactions {
+    pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let txs = if self.cached {
+            state.get_transactions(&options.wallet).context("Failed to load transactions")?
+        } else {
+            state.fetch_transactions(&options.wallet).await.context("Failed to fetch transactions")?
+        };
+
+        let addresses = state.address_types(&options.wallet)?;
+        let mut transactions = vec![];
+        for tx in txs {
+            let effect = tx_effect(&addresses, &tx);
+            transactions.push(OutputTransaction {
+                txid: tx.txid,
+                kind: effect.kind,
+                received: effect.received,
+                sent: effect.sent,
+                fee: effect.fee,
+                net: effect.net,
+                inscription_moved: effect.inscription_moved,
+                confirmed: tx.status.confirmed,
+                height: tx.status.block_height,
+                time: tx.status.block_time.map(|x| crate::timestamp(x).to_rfc3339()),
+            });
+        }
+
+        print_json(Output { transactions })
+    }
+}
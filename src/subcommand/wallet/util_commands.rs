@@ -23,11 +23,13 @@ pub struct AddAddress {
 
 impl AddAddress {
     pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        let address = bitcoin::Address::from_str(&self.address).context("Invalid public address")?;
+        state.check_address(&address)?;
         let private = self.private.as_deref().map(SecretKey::from_str).transpose().context("Invalid private address")?;
-        state.push_address(&self.address.to_string(), &WalletAddressData {
-            private,
-            ty: self.ty,
-        }, &options.wallet)?;
+        if private.is_some() {
+            super::unlock(&options, &state)?;
+        }
+        state.push_address(&address.to_string(), &WalletAddressData::new(private, self.ty), &options.wallet)?;
 
         Ok(())
     }
@@ -55,6 +57,7 @@ pub struct ListAddressesOutputItem {
     pub address: String,
     pub private: Option<String>,
     #[serde(rename="type")] pub ty: AddressType,
+    pub path: Option<bitcoin::util::bip32::DerivationPath>,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -63,13 +66,15 @@ pub struct ListAddresses {
 
 impl ListAddresses {
     pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
+        super::unlock(&options, &state)?;
         let items = state.addresses(&options.wallet)?
-            .map(|x| ListAddressesOutputItem {
+            .map(|x| Ok(ListAddressesOutputItem {
+                private: state.address_private(&options.wallet, &x.0, &x.1)?.map(|x| format!("{}", x.display_secret())),
                 address: x.0,
-                private: x.1.private.map(|x| format!("{}", x.display_secret())),
                 ty: x.1.ty,
-            })
-            .collect();
+                path: x.1.derivation,
+            }))
+            .collect::<anyhow::Result<_>>()?;
         print_json(ListAddressesOutput {
             addresses: items,
         }).unwrap();
@@ -91,8 +96,9 @@ pub struct GetPrivate {
 impl GetPrivate {
     pub async fn run(self, options: crate::subcommand::Options, state: Arc<Minter>) -> anyhow::Result<()> {
         let addr = state.get_address(&options.wallet, &self.address)?.context("Address not found")?;
+        super::unlock(&options, &state)?;
         print_json(GetPrivateOutput {
-            private: addr.private.map(|x|format!("{}", x.display_secret())),
+            private: state.address_private(&options.wallet, &self.address, &addr)?.map(|x|format!("{}", x.display_secret())),
         }).unwrap();
         Ok(())
     }
@@ -122,9 +128,11 @@ impl ImportYaml {
         
         let wallet = state.create_wallet_with_mnemonic(wallet.passphrase.unwrap_or_else(||"bells".to_owned()), options.wallet, wallet.mnemonic).context("Failed to import wallet")?;
 
+        let (mnemonic, passphrase) = state.wallet_secrets(&wallet)?;
+
 		print_json(ImportYamlOutput {
-			mnemonic: bip39::Mnemonic::from_str(&wallet.mnemonic).unwrap(),
-			passphrase: Some(wallet.passphrase.unwrap_or_else(||"bells".to_owned())),
+			mnemonic: bip39::Mnemonic::from_str(&mnemonic).context("Invalid mnemonic")?,
+			passphrase: Some(passphrase.unwrap_or_else(||"bells".to_owned())),
 		})?;
 
         info!("Wallet imported");
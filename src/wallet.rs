@@ -1,4 +1,6 @@
-use bitcoin::secp256k1::SecretKey;
+use bitcoin::{secp256k1::SecretKey, util::bip32::{DerivationPath, ExtendedPubKey}};
+
+use crate::{chain::Chain, encryption::{EncryptedData, KdfParams}};
 
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, clap::ValueEnum, PartialEq, Eq)]
 pub enum AddressType {
@@ -8,35 +10,95 @@ pub enum AddressType {
 	Ord,
 }
 
+impl AddressType {
+	/// Index of BIP44 chain `m/44'/{coin_type}'/0'/{chain}` used for addresses of this type
+	pub fn chain(&self) -> u32 {
+		match self {
+			AddressType::Utxo => 0,
+			AddressType::Ord => 1,
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			AddressType::Utxo => "utxo",
+			AddressType::Ord => "ord",
+		}
+	}
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WalletAddressData {
 	pub(crate) private: Option<SecretKey>,
 	pub ty: AddressType,
+	/// Private key encrypted with wallet key. `private` is None if set
+	pub(crate) encrypted_private: Option<EncryptedData>,
+	/// Path of key derived from wallet seed. None for added addresses
+	pub derivation: Option<DerivationPath>,
+}
+
+impl WalletAddressData {
+	pub fn new(private: Option<SecretKey>, ty: AddressType) -> Self {
+		Self { private, ty, encrypted_private: None, derivation: None }
+	}
+
+	/// Address derived from wallet seed, or from xpub of watch-only wallet if `private` is None
+	pub fn derived(private: Option<SecretKey>, ty: AddressType, derivation: DerivationPath) -> Self {
+		Self { derivation: Some(derivation), ..Self::new(private, ty) }
+	}
+
+	pub fn has_private(&self) -> bool {
+		self.private.is_some() || self.encrypted_private.is_some()
+	}
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum WalletSecrets {
+	Plain {
+		mnemonic: String,
+		passphrase: Option<String>,
+	},
+	/// (mnemonic, passphrase) encrypted with key derived from password using `kdf`
+	Encrypted {
+		kdf: KdfParams,
+		data: EncryptedData,
+	},
+	/// No secrets. Addresses are derived from account `xpub` if it is set, otherwise they are imported
+	WatchOnly {
+		xpub: Option<ExtendedPubKey>,
+	},
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Wallet {
 	pub name: String,
-    pub mnemonic: String,
-    pub passphrase: Option<String>,
-    // pub addresses: Vec<WalletAddress>,
-    // utxos: Vec<u128>,
-    // inscriptions: Vec<u128>,
+	pub secrets: WalletSecrets,
+	/// Network wallet addresses are derived for
+	pub network: Chain,
 }
 
 impl Wallet {
-	pub fn new(mnemonic: String, passphrase: Option<String>, name: String) -> Self {
+	pub fn new(mnemonic: String, passphrase: Option<String>, name: String, network: Chain) -> Self {
 		Self {
 			name,
-			mnemonic,
-			passphrase,
-			// addresses: vec![],
-			// utxo_address_count: 0,
-			// inscription_address_count: 0,
+			secrets: WalletSecrets::Plain { mnemonic, passphrase },
+			network,
 		}
 	}
 
-	// pub fn add_address(&mut self, addr: WalletAddress) {
-	// 	// self.addresses.push(addr)
-	// }
-}
\ No newline at end of file
+	pub fn watch_only(xpub: Option<ExtendedPubKey>, name: String, network: Chain) -> Self {
+		Self {
+			name,
+			secrets: WalletSecrets::WatchOnly { xpub },
+			network,
+		}
+	}
+
+	pub fn is_encrypted(&self) -> bool {
+		matches!(self.secrets, WalletSecrets::Encrypted { .. })
+	}
+
+	pub fn is_watch_only(&self) -> bool {
+		matches!(self.secrets, WalletSecrets::WatchOnly { .. })
+	}
+}